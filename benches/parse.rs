@@ -2,7 +2,79 @@
 extern crate criterion;
 extern crate wkt;
 
-use criterion::Criterion;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use wkt::tokenizer::{PeekableTokens, Token, Tokens};
+use wkt::types::{Coord, Dimension};
+use wkt::{FromTokens, WktParser};
+
+/// Counts allocations, to compare the coordinate list strategies below.
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(new_size, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+/// Reads comma separated coordinates into `coords`, as `comma_many` does.
+fn read_coords(body: &str, mut coords: Vec<Coord<f64>>) -> Vec<Coord<f64>> {
+    let mut tokens = PeekableTokens::new(Tokens::from_str(body), WktParser::new());
+    loop {
+        coords.push(Coord::from_tokens(&mut tokens, Dimension::XY).unwrap());
+        match tokens.next() {
+            Some(Ok(Token::Comma)) => (),
+            _ => return coords,
+        }
+    }
+}
+
+/// Reads coordinates into a growing `Vec`, the current strategy of `comma_many`.
+fn grow_coords(body: &str) -> Vec<Coord<f64>> {
+    read_coords(body, Vec::new())
+}
+
+/// Tokenizes once to count the commas, then reads coordinates into a reserved `Vec`.
+fn count_then_reserve_coords(body: &str) -> Vec<Coord<f64>> {
+    let commas = Tokens::<f64>::from_str(body)
+        .filter(|token| matches!(token, Ok(Token::Comma)))
+        .count();
+    read_coords(body, Vec::with_capacity(commas + 1))
+}
+
+/// Prints the allocations made by one call of `f`.
+fn report_allocations<F: Fn(&str) -> Vec<Coord<f64>>>(name: &str, f: F, body: &str) {
+    let (count, bytes) = (
+        ALLOCATIONS.load(Ordering::Relaxed),
+        ALLOCATED_BYTES.load(Ordering::Relaxed),
+    );
+    let coords = f(body);
+    println!(
+        "{}: {} coordinates, {} allocations, {} bytes",
+        name,
+        coords.len(),
+        ALLOCATIONS.load(Ordering::Relaxed) - count,
+        ALLOCATED_BYTES.load(Ordering::Relaxed) - bytes,
+    );
+}
 
 fn criterion_benchmark(c: &mut criterion::Criterion) {
     c.bench_function("parse small", |bencher| {
@@ -18,7 +90,73 @@ fn criterion_benchmark(c: &mut criterion::Criterion) {
             let _ = wkt::Wkt::<f64>::from_str(s).unwrap();
         });
     });
+
+    c.bench_function("parse long linestring", |bencher| {
+        let coords: Vec<String> = (0..100_000)
+            .map(|i| format!("{}.25 {}.75", i, -i))
+            .collect();
+        let s = format!("LINESTRING ({})", coords.join(","));
+        bencher.iter(|| {
+            let _ = wkt::Wkt::<f64>::from_str(&s).unwrap();
+        });
+    });
+
+    c.bench_function("validate long linestring", |bencher| {
+        let coords: Vec<String> = (0..100_000)
+            .map(|i| format!("{}.25 {}.75", i, -i))
+            .collect();
+        let s = format!("LINESTRING ({})", coords.join(","));
+        bencher.iter(|| {
+            wkt::Wkt::<f64>::validate_str(&s).unwrap();
+        });
+    });
+
+    c.bench_function("parse large multipolygon", |bencher| {
+        let polygons: Vec<String> = (0..10_000)
+            .map(|i| {
+                let (x, y) = (i % 100, i / 100);
+                format!(
+                    "(({x} {y},{x}.5 {y},{x}.5 {y}.5,{x} {y}.5,{x} {y}))",
+                    x = x,
+                    y = y
+                )
+            })
+            .collect();
+        let s = format!("MULTIPOLYGON ({})", polygons.join(","));
+        bencher.iter(|| {
+            let _ = wkt::Wkt::<f64>::from_str(&s).unwrap();
+        });
+    });
+}
+
+/// Two million comma separated coordinates, the body of a long `LINESTRING`
+fn coord_list() -> String {
+    let coords: Vec<String> = (0..2_000_000)
+        .map(|i| format!("{}.25 {}.75", i, -i))
+        .collect();
+    coords.join(",")
+}
+
+fn coord_list_benchmark(c: &mut criterion::Criterion) {
+    let body = coord_list();
+    report_allocations("grow", grow_coords, &body);
+    report_allocations("count then reserve", count_then_reserve_coords, &body);
+
+    c.bench_function("coords grow", |bencher| {
+        let body = coord_list();
+        bencher.iter(|| grow_coords(&body));
+    });
+
+    c.bench_function("coords count then reserve", |bencher| {
+        let body = coord_list();
+        bencher.iter(|| count_then_reserve_coords(&body));
+    });
 }
 
 criterion_group!(benches, criterion_benchmark);
-criterion_main!(benches);
+criterion_group! {
+    name = coord_lists;
+    config = criterion::Criterion::default().sample_size(10);
+    targets = coord_list_benchmark
+}
+criterion_main!(benches, coord_lists);
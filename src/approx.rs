@@ -0,0 +1,118 @@
+// Copyright 2014-2021 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use types::{Coord, LineString, Point, Polygon};
+use {Geometry, WktFloat};
+
+impl<T> Geometry<T>
+where
+    T: WktFloat,
+{
+    /// Whether the geometries have the same type and structure, including that of nested
+    /// collections, and their coordinates differ by at most `epsilon` in each ordinate, for
+    /// example to compare geometries after reprojection or rounding. Dimension tags are
+    /// ignored, but a `z` or `m` must be present in both coordinates or neither.
+    pub fn approx_eq(&self, other: &Geometry<T>, epsilon: T) -> bool {
+        let coords =
+            |a: &[Coord<T>], b: &[Coord<T>]| slices_eq(a, b, |a, b| coord_eq(a, b, epsilon));
+        let rings =
+            |a: &[LineString<T>], b: &[LineString<T>]| slices_eq(a, b, |a, b| coords(&a.0, &b.0));
+        let polygons =
+            |a: &[Polygon<T>], b: &[Polygon<T>]| slices_eq(a, b, |a, b| rings(&a.0, &b.0));
+        match (self, other) {
+            (Geometry::Point(a), Geometry::Point(b)) => point_eq(a, b, epsilon),
+            (Geometry::LineString(a), Geometry::LineString(b)) => coords(&a.0, &b.0),
+            (Geometry::CircularString(a), Geometry::CircularString(b)) => coords(&a.0, &b.0),
+            (Geometry::Polygon(a), Geometry::Polygon(b)) => rings(&a.0, &b.0),
+            (Geometry::Triangle(a), Geometry::Triangle(b)) => rings(&a.0, &b.0),
+            (Geometry::MultiLineString(a), Geometry::MultiLineString(b)) => rings(&a.0, &b.0),
+            (Geometry::MultiPoint(a), Geometry::MultiPoint(b)) => {
+                slices_eq(&a.0, &b.0, |a, b| point_eq(a, b, epsilon))
+            }
+            (Geometry::MultiPolygon(a), Geometry::MultiPolygon(b)) => polygons(&a.0, &b.0),
+            (Geometry::PolyhedralSurface(a), Geometry::PolyhedralSurface(b)) => {
+                polygons(&a.0, &b.0)
+            }
+            (Geometry::Tin(a), Geometry::Tin(b)) => slices_eq(&a.0, &b.0, |a, b| rings(&a.0, &b.0)),
+            (Geometry::GeometryCollection(a), Geometry::GeometryCollection(b)) => {
+                slices_eq(&a.0, &b.0, |a, b| a.approx_eq(b, epsilon))
+            }
+            (Geometry::Box(a), Geometry::Box(b)) => {
+                coord_eq(&a.min, &b.min, epsilon) && coord_eq(&a.max, &b.max, epsilon)
+            }
+            _ => false,
+        }
+    }
+}
+
+fn slices_eq<A, F>(a: &[A], b: &[A], eq: F) -> bool
+where
+    F: Fn(&A, &A) -> bool,
+{
+    a.len() == b.len() && a.iter().zip(b).all(|(a, b)| eq(a, b))
+}
+
+fn point_eq<T: WktFloat>(a: &Point<T>, b: &Point<T>, epsilon: T) -> bool {
+    match (&a.0, &b.0) {
+        (Some(a), Some(b)) => coord_eq(a, b, epsilon),
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+pub(crate) fn coord_eq<T: WktFloat>(a: &Coord<T>, b: &Coord<T>, epsilon: T) -> bool {
+    let close = |a: T, b: T| (a - b).abs() <= epsilon;
+    let optional = |a: Option<T>, b: Option<T>| match (a, b) {
+        (Some(a), Some(b)) => close(a, b),
+        (None, None) => true,
+        _ => false,
+    };
+    close(a.x, b.x) && close(a.y, b.y) && optional(a.z, b.z) && optional(a.m, b.m)
+}
+
+#[cfg(test)]
+mod tests {
+    use Wkt;
+
+    #[test]
+    fn approx_eq_points() {
+        let a: Wkt<f64> = Wkt::from_str("POINT (1 2)").unwrap();
+        let b: Wkt<f64> = Wkt::from_str("POINT (1.000000001 2.0)").unwrap();
+        let c: Wkt<f64> = Wkt::from_str("POINT (2 2)").unwrap();
+        assert!(a.items[0].approx_eq(&b.items[0], 1e-6));
+        assert!(!a.items[0].approx_eq(&c.items[0], 1e-6));
+        assert!(a.items[0].approx_eq(&c.items[0], 1.0));
+    }
+
+    #[test]
+    fn approx_eq_collections() {
+        let a: Wkt<f64> = Wkt::from_str(
+            "GEOMETRYCOLLECTION (POINT Z (1 2 3), MULTIPOLYGON (((0 0, 1 0, 1 1, 0 0))))",
+        )
+        .unwrap();
+        let b: Wkt<f64> = Wkt::from_str(
+            "GEOMETRYCOLLECTION (POINT Z (1 2 3.0000000001), MULTIPOLYGON (((0 0, 1 0, 1 1, 0 0))))",
+        )
+        .unwrap();
+        assert!(a.items[0].approx_eq(&b.items[0], 1e-9));
+
+        for other in &[
+            "GEOMETRYCOLLECTION (POINT (1 2), MULTIPOLYGON (((0 0, 1 0, 1 1, 0 0))))",
+            "GEOMETRYCOLLECTION (POINT Z (1 2 3), MULTIPOLYGON (((0 0, 1 0, 1 1, 0 0)), ((0 0, 1 0, 1 1, 0 0))))",
+            "GEOMETRYCOLLECTION (POINT Z (1 2 3), POLYGON ((0 0, 1 0, 1 1, 0 0)))",
+        ] {
+            let other: Wkt<f64> = Wkt::from_str(other).unwrap();
+            assert!(!a.items[0].approx_eq(&other.items[0], 1e-9));
+        }
+    }
+}
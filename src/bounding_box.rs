@@ -0,0 +1,314 @@
+// Copyright 2014-2021 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::str::FromStr;
+use types::{Coord, Dimension, LineString, Polygon};
+use {Geometry, Wkt, WktFloat};
+
+/// The extent and coordinate count of a [`Wkt`], gathered in a single traversal by
+/// [`Wkt::coord_stats`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct CoordStats<T>
+where
+    T: WktFloat,
+{
+    /// The minimum corner, carrying a `z` when any coordinate has one
+    pub min: Coord<T>,
+    /// The maximum corner, carrying a `z` when any coordinate has one
+    pub max: Coord<T>,
+    /// The number of coordinates over all items
+    pub count: usize,
+}
+
+impl<T> Geometry<T>
+where
+    T: WktFloat,
+{
+    /// The minimum and maximum corners of the axis-aligned box enclosing the geometry,
+    /// or `None` if it is empty. The corners carry a `z` when any coordinate has one.
+    pub fn bounding_box(&self) -> Option<(Coord<T>, Coord<T>)> {
+        self.coords().fold(None, expand)
+    }
+
+    /// The two dimensional bounding box as a closed, counter-clockwise five coordinate
+    /// polygon, or `None` if the geometry is empty.
+    pub fn extent_polygon(&self) -> Option<Polygon<T>> {
+        let (min, max) = self.bounding_box()?;
+        let corner = |x, y| Coord {
+            x,
+            y,
+            z: None,
+            m: None,
+        };
+        let ring = vec![
+            corner(min.x, min.y),
+            corner(max.x, min.y),
+            corner(max.x, max.y),
+            corner(min.x, max.y),
+            corner(min.x, min.y),
+        ];
+        Some(Polygon(
+            vec![LineString(ring, Dimension::XY)],
+            Dimension::XY,
+        ))
+    }
+
+    /// The minimum-area rectangle enclosing the geometry in the plane, in any orientation,
+    /// as a closed, counter-clockwise five coordinate polygon. It is tighter than the
+    /// [`Geometry::extent_polygon`] of elongated diagonal features. One side of the
+    /// rectangle lies on an edge of the convex hull, so each edge is tried in turn. Returns
+    /// `None` if the geometry is empty.
+    pub fn oriented_bbox(&self) -> Option<Polygon<T>> {
+        let hull = convex_hull(self.coords().map(|c| (c.x, c.y)).collect());
+        let first = *hull.first()?;
+        let mut best: Option<(T, [(T, T); 4])> = None;
+        for i in 0..hull.len() {
+            let (a, b) = (hull[i], hull[(i + 1) % hull.len()]);
+            let len = (b.0 - a.0).hypot(b.1 - a.1);
+            if len.is_zero() && hull.len() > 1 {
+                continue;
+            }
+            // The axes of the rectangle, along the edge and perpendicular to it
+            let u = match len.is_zero() {
+                true => (T::one(), T::zero()),
+                false => ((b.0 - a.0) / len, (b.1 - a.1) / len),
+            };
+            let v = (-u.1, u.0);
+            let project = |p: &(T, T), axis: (T, T)| p.0 * axis.0 + p.1 * axis.1;
+            let (mut min_u, mut max_u) = (project(&first, u), project(&first, u));
+            let (mut min_v, mut max_v) = (project(&first, v), project(&first, v));
+            for p in &hull {
+                min_u = min_u.min(project(p, u));
+                max_u = max_u.max(project(p, u));
+                min_v = min_v.min(project(p, v));
+                max_v = max_v.max(project(p, v));
+            }
+            let area = (max_u - min_u) * (max_v - min_v);
+            if best.as_ref().is_none_or(|(best_area, _)| area < *best_area) {
+                let corner = |s: T, t: T| (u.0 * s + v.0 * t, u.1 * s + v.1 * t);
+                let corners = [
+                    corner(min_u, min_v),
+                    corner(max_u, min_v),
+                    corner(max_u, max_v),
+                    corner(min_u, max_v),
+                ];
+                best = Some((area, corners));
+            }
+        }
+
+        let (_, corners) = best?;
+        let ring = corners
+            .iter()
+            .chain(corners.first())
+            .map(|&(x, y)| Coord {
+                x,
+                y,
+                z: None,
+                m: None,
+            })
+            .collect();
+        Some(Polygon(
+            vec![LineString(ring, Dimension::XY)],
+            Dimension::XY,
+        ))
+    }
+}
+
+/// The convex hull of `points` in counter-clockwise order, without repeating the first
+/// point, by Andrew's monotone chain.
+fn convex_hull<T: WktFloat>(mut points: Vec<(T, T)>) -> Vec<(T, T)> {
+    points.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    points.dedup();
+    if points.len() < 3 {
+        return points;
+    }
+
+    let mut hull: Vec<(T, T)> = Vec::with_capacity(2 * points.len());
+    for &p in &points {
+        push_left_turn(&mut hull, 0, p);
+    }
+    // The last point of each chain begins the other one.
+    hull.pop();
+    let start = hull.len();
+    for &p in points.iter().rev() {
+        push_left_turn(&mut hull, start, p);
+    }
+    hull.pop();
+    hull
+}
+
+/// Pushes `p` onto the chain of `hull` beginning at `start`, first popping the points
+/// which would not make a left turn.
+fn push_left_turn<T: WktFloat>(hull: &mut Vec<(T, T)>, start: usize, p: (T, T)) {
+    while hull.len() >= start + 2 {
+        let (o, a) = (hull[hull.len() - 2], hull[hull.len() - 1]);
+        if (a.0 - o.0) * (p.1 - o.1) - (a.1 - o.1) * (p.0 - o.0) > T::zero() {
+            break;
+        }
+        hull.pop();
+    }
+    hull.push(p);
+}
+
+impl<T> Wkt<T>
+where
+    T: WktFloat + FromStr + Default,
+{
+    /// The bounding box of all items, see [`Geometry::bounding_box`].
+    pub fn bounding_box(&self) -> Option<(Coord<T>, Coord<T>)> {
+        self.items
+            .iter()
+            .flat_map(Geometry::coords)
+            .fold(None, expand)
+    }
+
+    /// The bounding box and the total coordinate count of all items in one pass, or `None`
+    /// if there are no coordinates.
+    pub fn coord_stats(&self) -> Option<CoordStats<T>> {
+        let mut count = 0;
+        let (min, max) = self
+            .items
+            .iter()
+            .flat_map(Geometry::coords)
+            .inspect(|_| count += 1)
+            .fold(None, expand)?;
+        Some(CoordStats { min, max, count })
+    }
+}
+
+pub(crate) fn expand<T: WktFloat>(
+    mut bbox: Option<(Coord<T>, Coord<T>)>,
+    coord: &Coord<T>,
+) -> Option<(Coord<T>, Coord<T>)> {
+    let (min, max) = bbox.get_or_insert_with(|| {
+        let corner = Coord {
+            x: coord.x,
+            y: coord.y,
+            z: coord.z,
+            m: None,
+        };
+        (corner.clone(), corner)
+    });
+    min.x = min.x.min(coord.x);
+    min.y = min.y.min(coord.y);
+    max.x = max.x.max(coord.x);
+    max.y = max.y.max(coord.y);
+    if let Some(z) = coord.z {
+        min.z = Some(min.z.map_or(z, |min_z| min_z.min(z)));
+        max.z = Some(max.z.map_or(z, |max_z| max_z.max(z)));
+    }
+    bbox
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CoordStats;
+    use types::Coord;
+    use Wkt;
+
+    fn coord(x: f64, y: f64, z: Option<f64>) -> Coord<f64> {
+        Coord { x, y, z, m: None }
+    }
+
+    #[test]
+    fn linestring_bounding_box() {
+        let wkt: Wkt<f64> = Wkt::from_str("LINESTRING (10 -20, -5 3, 7 40)").unwrap();
+        assert_eq!(
+            Some((coord(-5., -20., None), coord(10., 40., None))),
+            wkt.items[0].bounding_box()
+        );
+    }
+
+    #[test]
+    fn linestring_extent_polygon() {
+        let wkt: Wkt<f64> = Wkt::from_str("LINESTRING (10 -20, -5 3, 7 40)").unwrap();
+        let extent = wkt.items[0].extent_polygon().unwrap();
+        assert_eq!(
+            "POLYGON((-5 -20,10 -20,10 40,-5 40,-5 -20))",
+            extent.to_string()
+        );
+        assert!(extent.validate().is_ok());
+
+        let wkt: Wkt<f64> = Wkt::from_str("LINESTRING EMPTY").unwrap();
+        assert!(wkt.items[0].extent_polygon().is_none());
+    }
+
+    #[test]
+    fn diagonal_oriented_bbox() {
+        let wkt: Wkt<f64> = Wkt::from_str("LINESTRING (0 0, 4 5, 10 10, 6 5)").unwrap();
+        let oriented = wkt.items[0].oriented_bbox().unwrap();
+        let extent = wkt.items[0].extent_polygon().unwrap();
+        let (oriented_area, _) = oriented.area_and_centroid().unwrap();
+        let (extent_area, _) = extent.area_and_centroid().unwrap();
+        assert!(oriented_area < extent_area / 4., "{}", oriented_area);
+        assert!(oriented.validate().is_ok());
+
+        // Every coordinate lies within the rectangle, up to rounding.
+        let ring = &oriented.0[0].0;
+        for c in wkt.items[0].coords() {
+            for side in ring.windows(2) {
+                let (a, b) = (&side[0], &side[1]);
+                let cross = (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x);
+                assert!(cross > -1e-9, "{:?} is outside {}", c, oriented);
+            }
+        }
+
+        let wkt: Wkt<f64> = Wkt::from_str("POINT (1 2)").unwrap();
+        assert_eq!(
+            "POLYGON((1 2,1 2,1 2,1 2,1 2))",
+            wkt.items[0].oriented_bbox().unwrap().to_string()
+        );
+        let wkt: Wkt<f64> = Wkt::from_str("LINESTRING EMPTY").unwrap();
+        assert!(wkt.items[0].oriented_bbox().is_none());
+    }
+
+    #[test]
+    fn multipolygon_bounding_box() {
+        let wkt: Wkt<f64> = Wkt::from_str(
+            "MULTIPOLYGON Z (((0 0 1, 4 0 2, 4 4 3, 0 0 1)), ((10 10 -1, 12 10 0, 12 13 0, 10 10 -1)))",
+        )
+        .unwrap();
+        assert_eq!(
+            Some((coord(0., 0., Some(-1.)), coord(12., 13., Some(3.)))),
+            wkt.bounding_box()
+        );
+    }
+
+    #[test]
+    fn empty_bounding_box() {
+        let wkt: Wkt<f64> =
+            Wkt::from_str("GEOMETRYCOLLECTION (POINT EMPTY, LINESTRING EMPTY)").unwrap();
+        assert_eq!(None, wkt.items[0].bounding_box());
+        assert_eq!(None, <Wkt<f64>>::new().bounding_box());
+    }
+
+    #[test]
+    fn mixed_dimension_coord_stats() {
+        let wkt: Wkt<f64> = Wkt::from_str(
+            "GEOMETRYCOLLECTION (POINT Z (1 2 3), LINESTRING (0 5, 4 -1), POINT M (2 2 9))",
+        )
+        .unwrap();
+        assert_eq!(
+            Some(CoordStats {
+                min: coord(0., -1., Some(3.)),
+                max: coord(4., 5., Some(3.)),
+                count: 4,
+            }),
+            wkt.coord_stats()
+        );
+        assert_eq!(None, <Wkt<f64>>::new().coord_stats());
+    }
+}
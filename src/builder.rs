@@ -0,0 +1,296 @@
+// Copyright 2014-2021 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Builders constructing geometries in code, validating them in `build`.
+//!
+//! ```
+//! use wkt::LineStringBuilder;
+//!
+//! let linestring = LineStringBuilder::new()
+//!     .push(1.0, 2.0)
+//!     .push(3.0, 4.0)
+//!     .build()
+//!     .unwrap();
+//! assert_eq!("LINESTRING(1 2,3 4)", linestring.to_string());
+//! ```
+
+use alloc::vec::Vec;
+use types::{Coord, Dimension, LineString, Point, Polygon};
+use {WktError, WktFloat};
+
+/// Builds a [`Point`] with optional `z` and `m` ordinates.
+#[derive(Clone, Debug)]
+pub struct PointBuilder<T: WktFloat> {
+    coord: Coord<T>,
+}
+
+impl<T> PointBuilder<T>
+where
+    T: WktFloat,
+{
+    /// Starts a point at `x` and `y`.
+    pub fn new(x: T, y: T) -> Self {
+        PointBuilder {
+            coord: Coord {
+                x,
+                y,
+                z: None,
+                m: None,
+            },
+        }
+    }
+
+    /// Sets the `z` ordinate.
+    pub fn z(mut self, z: T) -> Self {
+        self.coord.z = Some(z);
+        self
+    }
+
+    /// Sets the `m` ordinate.
+    pub fn m(mut self, m: T) -> Self {
+        self.coord.m = Some(m);
+        self
+    }
+
+    /// Builds the point, failing if an ordinate is not finite.
+    pub fn build(self) -> Result<Point<T>, WktError> {
+        check_finite(&self.coord)?;
+        let dim = self.coord.dimension();
+        Ok(Point(Some(self.coord), dim))
+    }
+}
+
+/// Builds a [`LineString`] one coordinate at a time.
+#[derive(Clone, Debug)]
+pub struct LineStringBuilder<T: WktFloat> {
+    coords: Vec<Coord<T>>,
+}
+
+impl<T> LineStringBuilder<T>
+where
+    T: WktFloat,
+{
+    /// Starts a linestring without coordinates.
+    pub fn new() -> Self {
+        LineStringBuilder { coords: Vec::new() }
+    }
+
+    /// Appends a two dimensional coordinate.
+    pub fn push(self, x: T, y: T) -> Self {
+        self.push_coord(Coord {
+            x,
+            y,
+            z: None,
+            m: None,
+        })
+    }
+
+    /// Appends a coordinate, which may carry `z` and `m`.
+    pub fn push_coord(mut self, coord: Coord<T>) -> Self {
+        self.coords.push(coord);
+        self
+    }
+
+    /// Builds the linestring, failing if it has fewer than two coordinates, if an
+    /// ordinate is not finite, or if the coordinates differ in dimension.
+    pub fn build(self) -> Result<LineString<T>, WktError> {
+        if self.coords.len() < 2 {
+            return Err(WktError::TooFewCoordinates {
+                geometry: "LineString",
+                min: 2,
+                found: self.coords.len(),
+            });
+        }
+        let dim = check_coords(&self.coords)?;
+        Ok(LineString(self.coords, dim))
+    }
+}
+
+impl<T> Default for LineStringBuilder<T>
+where
+    T: WktFloat,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Builds a two dimensional [`Polygon`] from an exterior ring and any number of holes.
+/// Rings are closed automatically.
+#[derive(Clone, Debug)]
+pub struct PolygonBuilder<T: WktFloat> {
+    exterior: Vec<Coord<T>>,
+    holes: Vec<Vec<Coord<T>>>,
+}
+
+impl<T> PolygonBuilder<T>
+where
+    T: WktFloat,
+{
+    /// Starts a polygon without an exterior ring or holes.
+    pub fn new() -> Self {
+        PolygonBuilder {
+            exterior: Vec::new(),
+            holes: Vec::new(),
+        }
+    }
+
+    /// Sets the exterior ring from `(x, y)` pairs.
+    pub fn exterior<I: IntoIterator<Item = (T, T)>>(mut self, coords: I) -> Self {
+        self.exterior = ring(coords);
+        self
+    }
+
+    /// Adds an interior ring from `(x, y)` pairs.
+    pub fn add_hole<I: IntoIterator<Item = (T, T)>>(mut self, coords: I) -> Self {
+        self.holes.push(ring(coords));
+        self
+    }
+
+    /// Builds the polygon, failing if an ordinate is not finite or if a ring is too
+    /// short, see [`Polygon::validate`].
+    pub fn build(self) -> Result<Polygon<T>, WktError> {
+        for coord in self.exterior.iter().chain(self.holes.iter().flatten()) {
+            check_finite(coord)?;
+        }
+        Polygon::new(self.exterior, self.holes)
+    }
+}
+
+impl<T> Default for PolygonBuilder<T>
+where
+    T: WktFloat,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn ring<T: WktFloat, I: IntoIterator<Item = (T, T)>>(coords: I) -> Vec<Coord<T>> {
+    coords
+        .into_iter()
+        .map(|(x, y)| Coord {
+            x,
+            y,
+            z: None,
+            m: None,
+        })
+        .collect()
+}
+
+fn check_finite<T: WktFloat>(coord: &Coord<T>) -> Result<(), WktError> {
+    let ordinates = [Some(coord.x), Some(coord.y), coord.z, coord.m];
+    match ordinates.iter().flatten().find(|value| !value.is_finite()) {
+        Some(value) => Err(WktError::InvalidNumber(format!("{:?}", value))),
+        None => Ok(()),
+    }
+}
+
+/// Checks every coordinate is finite and of the same dimension, which is returned.
+fn check_coords<T: WktFloat>(coords: &[Coord<T>]) -> Result<Dimension, WktError> {
+    let expected = coords.first().map_or(Dimension::XY, Coord::dimension);
+    for coord in coords {
+        check_finite(coord)?;
+        let found = coord.dimension();
+        if found != expected {
+            return Err(WktError::InconsistentDimension { expected, found });
+        }
+    }
+    Ok(expected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LineStringBuilder, PointBuilder, PolygonBuilder};
+    use types::{Coord, Dimension};
+    use WktError;
+
+    #[test]
+    fn build_point() {
+        let point = PointBuilder::new(1.0, 2.0).build().unwrap();
+        assert_eq!("POINT(1 2)", point.to_string());
+
+        let point = PointBuilder::new(1.0, 2.0).z(3.0).m(4.0).build().unwrap();
+        assert_eq!(Dimension::XYZM, point.1);
+        assert_eq!("POINT ZM(1 2 3 4)", point.to_string());
+
+        assert_eq!(
+            WktError::InvalidNumber("NaN".to_string()),
+            PointBuilder::new(1.0, f64::NAN).build().unwrap_err()
+        );
+    }
+
+    #[test]
+    fn build_linestring() {
+        let linestring = LineStringBuilder::new()
+            .push(1.0, 2.0)
+            .push(3.0, 4.0)
+            .build()
+            .unwrap();
+        assert_eq!("LINESTRING(1 2,3 4)", linestring.to_string());
+
+        assert_eq!(
+            WktError::TooFewCoordinates {
+                geometry: "LineString",
+                min: 2,
+                found: 1,
+            },
+            LineStringBuilder::new().push(1.0, 2.0).build().unwrap_err()
+        );
+
+        let error = LineStringBuilder::new()
+            .push(1.0, 2.0)
+            .push_coord(Coord {
+                x: 3.0,
+                y: 4.0,
+                z: Some(5.0),
+                m: None,
+            })
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            WktError::InconsistentDimension {
+                expected: Dimension::XY,
+                found: Dimension::XYZ,
+            },
+            error
+        );
+    }
+
+    #[test]
+    fn build_polygon() {
+        let polygon = PolygonBuilder::new()
+            .exterior(vec![(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)])
+            .add_hole(vec![(2.0, 2.0), (2.0, 4.0), (4.0, 4.0)])
+            .build()
+            .unwrap();
+        assert_eq!(
+            "POLYGON((0 0,10 0,10 10,0 10,0 0),(2 2,2 4,4 4,2 2))",
+            polygon.to_string()
+        );
+
+        let error = PolygonBuilder::new()
+            .exterior(vec![(0.0, 0.0), (1.0, 1.0)])
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            WktError::TooFewCoordinates {
+                geometry: "LinearRing",
+                min: 4,
+                found: 3,
+            },
+            error
+        );
+    }
+}
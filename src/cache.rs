@@ -0,0 +1,459 @@
+// Copyright 2014-2021 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A crate-specific binary dump of [`Wkt`], faster to load than WKT text.
+//!
+//! The layout is `WKTC`, a version byte, then a little-endian `u32` item count followed by
+//! each item, its comment, its feature id as a `u64` and its span as two `u64`s, each of
+//! the last three behind a byte flagging its presence. An item is a type tag byte and a
+//! dimension byte followed by its parts, where every list is prefixed with its `u32` length
+//! and every coordinate with a byte flagging its `z` (bit 0) and `m` (bit 1); the dimension
+//! byte uses the same bits. Ordinates are stored as `f64`.
+
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::str::FromStr;
+use parser::DEFAULT_MAX_DEPTH;
+use types::*;
+use {Geometry, Wkt, WktError, WktFloat};
+
+const MAGIC: &[u8] = b"WKTC";
+const VERSION: u8 = 3;
+
+const POINT: u8 = 1;
+const LINESTRING: u8 = 2;
+const POLYGON: u8 = 3;
+const MULTIPOINT: u8 = 4;
+const MULTILINESTRING: u8 = 5;
+const MULTIPOLYGON: u8 = 6;
+const GEOMETRYCOLLECTION: u8 = 7;
+const TRIANGLE: u8 = 8;
+const POLYHEDRALSURFACE: u8 = 9;
+const TIN: u8 = 10;
+const CIRCULARSTRING: u8 = 11;
+const BOX: u8 = 12;
+
+impl<T> Wkt<T>
+where
+    T: WktFloat + FromStr + Default,
+{
+    /// Dumps the items along with their comments, feature ids and spans to the crate's
+    /// compact binary cache format.
+    pub fn to_cache_bytes(&self) -> Vec<u8> {
+        let mut out = MAGIC.to_vec();
+        out.push(VERSION);
+        write_len(&mut out, self.items.len());
+        for (i, item) in self.items.iter().enumerate() {
+            write_geometry(&mut out, item);
+            match self.comments.get(i).and_then(Option::as_ref) {
+                Some(comment) => {
+                    out.push(1);
+                    write_len(&mut out, comment.len());
+                    out.extend_from_slice(comment.as_bytes());
+                }
+                None => out.push(0),
+            }
+            match self.ids.get(i).cloned().flatten() {
+                Some(id) => {
+                    out.push(1);
+                    out.extend_from_slice(&id.to_le_bytes());
+                }
+                None => out.push(0),
+            }
+            match self.spans.get(i).cloned().flatten() {
+                Some((start, end)) => {
+                    out.push(1);
+                    out.extend_from_slice(&(start as u64).to_le_bytes());
+                    out.extend_from_slice(&(end as u64).to_le_bytes());
+                }
+                None => out.push(0),
+            }
+        }
+        out
+    }
+
+    /// Loads bytes written by [`Wkt::to_cache_bytes`].
+    pub fn from_cache_bytes(bytes: &[u8]) -> Result<Self, WktError> {
+        let mut reader = Reader { bytes, depth: 0 };
+        if reader.take(MAGIC.len())? != MAGIC {
+            return Err(WktError::InvalidCache("missing header"));
+        }
+        if reader.byte()? != VERSION {
+            return Err(WktError::InvalidCache("unsupported version"));
+        }
+
+        let mut wkt = Wkt::new();
+        for _ in 0..reader.len()? {
+            wkt.add_item(reader.geometry()?);
+            if reader.byte()? == 1 {
+                let len = reader.len()?;
+                let comment = core::str::from_utf8(reader.take(len)?)
+                    .map_err(|_| WktError::InvalidCache("comment is not UTF-8"))?;
+                *wkt.comments.last_mut().unwrap() = Some(comment.to_string());
+            }
+            if reader.byte()? == 1 {
+                *wkt.ids.last_mut().unwrap() = Some(reader.u64()?);
+            }
+            if reader.byte()? == 1 {
+                let start = reader.offset()?;
+                let end = reader.offset()?;
+                *wkt.spans.last_mut().unwrap() = Some((start, end));
+            }
+        }
+        if !reader.bytes.is_empty() {
+            return Err(WktError::InvalidCache("trailing bytes"));
+        }
+        Ok(wkt)
+    }
+}
+
+fn write_len(out: &mut Vec<u8>, len: usize) {
+    out.extend_from_slice(&(len as u32).to_le_bytes());
+}
+
+fn write_f64<T: WktFloat>(out: &mut Vec<u8>, value: T) {
+    let value = value.to_f64().unwrap_or(f64::NAN);
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+fn write_header(out: &mut Vec<u8>, tag: u8, dim: Dimension) {
+    out.push(tag);
+    out.push(dim.has_z() as u8 | (dim.has_m() as u8) << 1);
+}
+
+fn write_coords<'a, T, I>(out: &mut Vec<u8>, coords: I)
+where
+    T: WktFloat + 'a,
+    I: ExactSizeIterator<Item = &'a Coord<T>>,
+{
+    write_len(out, coords.len());
+    for coord in coords {
+        out.push(coord.z.is_some() as u8 | (coord.m.is_some() as u8) << 1);
+        write_f64(out, coord.x);
+        write_f64(out, coord.y);
+        coord.z.into_iter().for_each(|z| write_f64(out, z));
+        coord.m.into_iter().for_each(|m| write_f64(out, m));
+    }
+}
+
+fn write_rings<T: WktFloat>(out: &mut Vec<u8>, rings: &[LineString<T>]) {
+    write_len(out, rings.len());
+    for ring in rings {
+        write_coords(out, ring.0.iter());
+    }
+}
+
+fn write_geometry<T: WktFloat>(out: &mut Vec<u8>, geometry: &Geometry<T>) {
+    match geometry {
+        Geometry::Point(point) => {
+            write_header(out, POINT, point.1);
+            write_coords(out, point.0.iter());
+        }
+        Geometry::LineString(linestring) => {
+            write_header(out, LINESTRING, linestring.1);
+            write_coords(out, linestring.0.iter());
+        }
+        Geometry::CircularString(circularstring) => {
+            write_header(out, CIRCULARSTRING, circularstring.1);
+            write_coords(out, circularstring.0.iter());
+        }
+        Geometry::Box(bbox) => {
+            write_header(out, BOX, bbox.dimension());
+            write_coords(out, [&bbox.min, &bbox.max].iter().cloned());
+        }
+        Geometry::Polygon(polygon) => {
+            write_header(out, POLYGON, polygon.1);
+            write_rings(out, &polygon.0);
+        }
+        Geometry::Triangle(triangle) => {
+            write_header(out, TRIANGLE, triangle.1);
+            write_rings(out, &triangle.0);
+        }
+        Geometry::MultiLineString(multilinestring) => {
+            write_header(out, MULTILINESTRING, multilinestring.1);
+            write_rings(out, &multilinestring.0);
+        }
+        Geometry::MultiPoint(multipoint) => {
+            write_header(out, MULTIPOINT, multipoint.1);
+            write_len(out, multipoint.0.len());
+            for point in &multipoint.0 {
+                write_coords(out, point.0.iter());
+            }
+        }
+        Geometry::MultiPolygon(MultiPolygon(polygons, dim))
+        | Geometry::PolyhedralSurface(PolyhedralSurface(polygons, dim)) => {
+            let tag = match geometry {
+                Geometry::MultiPolygon(_) => MULTIPOLYGON,
+                _ => POLYHEDRALSURFACE,
+            };
+            write_header(out, tag, *dim);
+            write_len(out, polygons.len());
+            for polygon in polygons {
+                write_rings(out, &polygon.0);
+            }
+        }
+        Geometry::Tin(tin) => {
+            write_header(out, TIN, tin.1);
+            write_len(out, tin.0.len());
+            for triangle in &tin.0 {
+                write_rings(out, &triangle.0);
+            }
+        }
+        Geometry::GeometryCollection(collection) => {
+            write_header(out, GEOMETRYCOLLECTION, collection.1);
+            write_len(out, collection.0.len());
+            for geometry in &collection.0 {
+                write_geometry(out, geometry);
+            }
+        }
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    /// How many geometry collections enclose the geometry being read
+    depth: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], WktError> {
+        if self.bytes.len() < len {
+            return Err(WktError::InvalidCache("unexpected end of input"));
+        }
+        let (head, tail) = self.bytes.split_at(len);
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    fn byte(&mut self) -> Result<u8, WktError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn len(&mut self) -> Result<usize, WktError> {
+        let mut buf = [0; 4];
+        buf.copy_from_slice(self.take(4)?);
+        Ok(u32::from_le_bytes(buf) as usize)
+    }
+
+    fn u64(&mut self) -> Result<u64, WktError> {
+        let mut buf = [0; 8];
+        buf.copy_from_slice(self.take(8)?);
+        Ok(u64::from_le_bytes(buf))
+    }
+
+    /// Reads a byte offset of a span.
+    fn offset(&mut self) -> Result<usize, WktError> {
+        usize::try_from(self.u64()?).map_err(|_| WktError::InvalidCache("span out of range"))
+    }
+
+    fn float<T: WktFloat>(&mut self) -> Result<T, WktError> {
+        let mut buf = [0; 8];
+        buf.copy_from_slice(self.take(8)?);
+        T::from(f64::from_le_bytes(buf)).ok_or(WktError::InvalidCache("ordinate out of range"))
+    }
+
+    fn dimension(&mut self) -> Result<Dimension, WktError> {
+        Ok(match self.byte()? {
+            0 => Dimension::XY,
+            1 => Dimension::XYZ,
+            2 => Dimension::XYM,
+            3 => Dimension::XYZM,
+            _ => return Err(WktError::InvalidCache("unknown dimension")),
+        })
+    }
+
+    fn coords<T: WktFloat>(&mut self) -> Result<Vec<Coord<T>>, WktError> {
+        let len = self.len()?;
+        // Every coordinate takes at least 17 bytes, which bounds the allocation.
+        let mut coords = Vec::with_capacity(len.min(self.bytes.len() / 17));
+        for _ in 0..len {
+            let flags = self.byte()?;
+            let x = self.float()?;
+            let y = self.float()?;
+            let z = if flags & 1 != 0 {
+                Some(self.float()?)
+            } else {
+                None
+            };
+            let m = if flags & 2 != 0 {
+                Some(self.float()?)
+            } else {
+                None
+            };
+            coords.push(Coord { x, y, z, m });
+        }
+        Ok(coords)
+    }
+
+    fn rings<T: WktFloat>(&mut self, dim: Dimension) -> Result<Vec<LineString<T>>, WktError> {
+        (0..self.len()?)
+            .map(|_| self.coords().map(|coords| LineString(coords, dim)))
+            .collect()
+    }
+
+    fn point<T: WktFloat>(&mut self, dim: Dimension) -> Result<Point<T>, WktError> {
+        let mut coords = self.coords()?;
+        if coords.len() > 1 {
+            return Err(WktError::InvalidCache("point with several coordinates"));
+        }
+        Ok(Point(coords.pop(), dim))
+    }
+
+    /// Reads the members of a geometry collection, nested in at most [`DEFAULT_MAX_DEPTH`]
+    /// others.
+    fn members<T: WktFloat>(&mut self) -> Result<Vec<Geometry<T>>, WktError> {
+        if self.depth >= DEFAULT_MAX_DEPTH {
+            return Err(WktError::InvalidCache("collections nested too deeply"));
+        }
+        self.depth += 1;
+        let members = (0..self.len()?).map(|_| self.geometry()).collect();
+        self.depth -= 1;
+        members
+    }
+
+    fn geometry<T: WktFloat>(&mut self) -> Result<Geometry<T>, WktError> {
+        let tag = self.byte()?;
+        let dim = self.dimension()?;
+        Ok(match tag {
+            POINT => Geometry::Point(self.point(dim)?),
+            LINESTRING => Geometry::LineString(LineString(self.coords()?, dim)),
+            CIRCULARSTRING => Geometry::CircularString(CircularString(self.coords()?, dim)),
+            BOX => {
+                let mut corners = self.coords()?;
+                match (corners.pop(), corners.pop(), corners.is_empty()) {
+                    (Some(max), Some(min), true) => Geometry::Box(Box2D { min, max }),
+                    _ => return Err(WktError::InvalidCache("box without two corners")),
+                }
+            }
+            POLYGON => Geometry::Polygon(Polygon(self.rings(dim)?, dim)),
+            TRIANGLE => Geometry::Triangle(Triangle(self.rings(dim)?, dim)),
+            MULTILINESTRING => Geometry::MultiLineString(MultiLineString(self.rings(dim)?, dim)),
+            MULTIPOINT => Geometry::MultiPoint(MultiPoint(
+                (0..self.len()?)
+                    .map(|_| self.point(dim))
+                    .collect::<Result<_, _>>()?,
+                dim,
+            )),
+            MULTIPOLYGON => Geometry::MultiPolygon(MultiPolygon(
+                (0..self.len()?)
+                    .map(|_| self.rings(dim).map(|rings| Polygon(rings, dim)))
+                    .collect::<Result<_, _>>()?,
+                dim,
+            )),
+            POLYHEDRALSURFACE => Geometry::PolyhedralSurface(PolyhedralSurface(
+                (0..self.len()?)
+                    .map(|_| self.rings(dim).map(|rings| Polygon(rings, dim)))
+                    .collect::<Result<_, _>>()?,
+                dim,
+            )),
+            TIN => Geometry::Tin(Tin(
+                (0..self.len()?)
+                    .map(|_| self.rings(dim).map(|rings| Triangle(rings, dim)))
+                    .collect::<Result<_, _>>()?,
+                dim,
+            )),
+            GEOMETRYCOLLECTION => {
+                Geometry::GeometryCollection(GeometryCollection(self.members()?, dim))
+            }
+            _ => return Err(WktError::InvalidCache("unknown geometry tag")),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parser::DEFAULT_MAX_DEPTH;
+    use {Geometry, Wkt, WktError, WktParser};
+
+    #[test]
+    fn round_trip_multilinestring() {
+        let wkt: Wkt<f64> =
+            Wkt::from_str("MULTILINESTRING ((10 20, 30.5 -40), (1 2, 3 4, 5 6))").unwrap();
+        let bytes = wkt.to_cache_bytes();
+        let loaded: Wkt<f64> = Wkt::from_cache_bytes(&bytes).unwrap();
+
+        assert_eq!(1, loaded.items.len());
+        assert_eq!(wkt.items[0].to_string(), loaded.items[0].to_string());
+        match &loaded.items[0] {
+            Geometry::MultiLineString(multilinestring) => {
+                assert_eq!(2, multilinestring.0.len());
+                assert_eq!(-40.0, multilinestring.0[0].0[1].y);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn round_trip_collection_with_z() {
+        let wkt: Wkt<f64> = Wkt::from_str(
+            "GEOMETRYCOLLECTION (POINT ZM (1 2 3 4), POINT M EMPTY, TIN Z (((0 0 0, 0 1 0, 1 0 1, 0 0 0))), BOX3D (0 0 0, 2 3 4))",
+        )
+        .unwrap();
+        let loaded: Wkt<f64> = Wkt::from_cache_bytes(&wkt.to_cache_bytes()).unwrap();
+        assert_eq!(wkt.items[0].to_string(), loaded.items[0].to_string());
+    }
+
+    #[test]
+    fn round_trip_ids_and_spans() {
+        let wkt: Wkt<f64> = WktParser::new()
+            .feature_ids(true)
+            .spans(true)
+            .parse("42: POINT (1 2)")
+            .unwrap();
+        let loaded: Wkt<f64> = Wkt::from_cache_bytes(&wkt.to_cache_bytes()).unwrap();
+        assert_eq!(vec![Some(42)], loaded.ids);
+        assert_eq!(wkt.spans, loaded.spans);
+        assert!(loaded.spans[0].is_some());
+
+        let wkt: Wkt<f64> = Wkt::from_str("POINT (1 2)").unwrap();
+        let loaded: Wkt<f64> = Wkt::from_cache_bytes(&wkt.to_cache_bytes()).unwrap();
+        assert_eq!(vec![None], loaded.ids);
+        assert_eq!(vec![None], loaded.spans);
+    }
+
+    #[test]
+    fn invalid_cache_bytes() {
+        let wkt: Wkt<f64> = Wkt::from_str("LINESTRING (10 20, 30 40)").unwrap();
+        let bytes = wkt.to_cache_bytes();
+
+        assert_eq!(
+            WktError::InvalidCache("unexpected end of input"),
+            <Wkt<f64>>::from_cache_bytes(&bytes[..bytes.len() - 3]).unwrap_err()
+        );
+        assert_eq!(
+            WktError::InvalidCache("missing header"),
+            <Wkt<f64>>::from_cache_bytes(b"POINT (1 2)").unwrap_err()
+        );
+    }
+
+    #[test]
+    fn nesting_limit_cache() {
+        let nested = |depth| {
+            let mut bytes = b"WKTC".to_vec();
+            bytes.push(super::VERSION);
+            bytes.extend_from_slice(&[1, 0, 0, 0]);
+            bytes.extend_from_slice(&[super::GEOMETRYCOLLECTION, 0, 1, 0, 0, 0].repeat(depth));
+            bytes.extend_from_slice(&[super::POINT, 0, 0, 0, 0, 0, 0, 0, 0]);
+            bytes
+        };
+        <Wkt<f64>>::from_cache_bytes(&nested(DEFAULT_MAX_DEPTH)).unwrap();
+        for &depth in &[DEFAULT_MAX_DEPTH + 1, 200_000] {
+            assert_eq!(
+                WktError::InvalidCache("collections nested too deeply"),
+                <Wkt<f64>>::from_cache_bytes(&nested(depth)).unwrap_err()
+            );
+        }
+    }
+}
@@ -12,11 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use alloc::boxed::Box;
+use alloc::vec::Vec;
 use types::*;
 use Geometry;
 use Wkt;
 
-use std::convert::{TryFrom, TryInto};
+use core::convert::{TryFrom, TryInto};
 
 use geo_types::CoordFloat;
 use thiserror::Error;
@@ -30,10 +32,14 @@ pub enum Error {
         expected: &'static str,
         found: &'static str,
     },
+    #[error("The WKT Triangle was empty, but geo_type::Triangles cannot be empty")]
+    TriangleConversionError,
+    #[error("The WKT {0} has no geo_types equivalent")]
+    UnsupportedGeometry(&'static str),
     #[error("Wrong number of Geometries: {0}")]
     WrongNumberOfGeometries(usize),
     #[error("External error: {0}")]
-    External(Box<dyn std::error::Error>),
+    External(Box<dyn core::error::Error>),
 }
 
 impl<T> TryFrom<Wkt<T>> for geo_types::Geometry<T>
@@ -46,12 +52,11 @@ where
         if wkt.items.len() == 1 {
             Self::try_from(wkt.items.pop().unwrap())
         } else {
-            Geometry::GeometryCollection(GeometryCollection(wkt.items)).try_into()
+            Geometry::GeometryCollection(GeometryCollection(wkt.items, Dimension::XY)).try_into()
         }
     }
 }
 
-#[macro_use]
 macro_rules! try_from_wkt_impl {
     ($($type: ident),+) => {
         $(
@@ -95,11 +100,11 @@ try_from_wkt_impl!(
     Triangle
 );
 
-impl<T> From<Coord<T>> for geo_types::Coordinate<T>
+impl<T> From<Coord<T>> for geo_types::Coord<T>
 where
     T: CoordFloat,
 {
-    fn from(coord: Coord<T>) -> geo_types::Coordinate<T> {
+    fn from(coord: Coord<T>) -> geo_types::Coord<T> {
         Self {
             x: coord.x,
             y: coord.y,
@@ -121,7 +126,7 @@ where
     }
 }
 
-#[deprecated(since = "0.9", note = "use `geometry.try_into()` instead")]
+#[deprecated(since = "0.9.0", note = "use `geometry.try_into()` instead")]
 pub fn try_into_geometry<T>(geometry: &Geometry<T>) -> Result<geo_types::Geometry<T>, Error>
 where
     T: CoordFloat,
@@ -146,7 +151,7 @@ where
         let coords = line_string
             .0
             .into_iter()
-            .map(geo_types::Coordinate::from)
+            .map(geo_types::Coord::from)
             .collect();
 
         geo_types::LineString(coords)
@@ -251,7 +256,65 @@ where
     }
 }
 
-#[deprecated(since = "0.9", note = "use `geometry_collection.try_into()` instead")]
+impl<T> TryFrom<Triangle<T>> for geo_types::Triangle<T>
+where
+    T: CoordFloat,
+{
+    type Error = Error;
+
+    fn try_from(triangle: Triangle<T>) -> Result<Self, Self::Error> {
+        let mut coords = triangle
+            .0
+            .into_iter()
+            .next()
+            .ok_or(Error::TriangleConversionError)?
+            .0
+            .into_iter()
+            .map(geo_types::Coord::from);
+        match (coords.next(), coords.next(), coords.next()) {
+            (Some(a), Some(b), Some(c)) => Ok(geo_types::Triangle::new(a, b, c)),
+            _ => Err(Error::TriangleConversionError),
+        }
+    }
+}
+
+impl<T> From<Box2D<T>> for geo_types::Rect<T>
+where
+    T: CoordFloat,
+{
+    fn from(bbox: Box2D<T>) -> Self {
+        geo_types::Rect::new(
+            geo_types::Coord::from(bbox.min),
+            geo_types::Coord::from(bbox.max),
+        )
+    }
+}
+
+/// geo-types has no polyhedral surface, so its patches become the polygons of a multipolygon
+impl<T> From<PolyhedralSurface<T>> for geo_types::MultiPolygon<T>
+where
+    T: CoordFloat,
+{
+    fn from(surface: PolyhedralSurface<T>) -> Self {
+        MultiPolygon(surface.0, surface.1).into()
+    }
+}
+
+/// geo-types has no TIN, so its triangles become the polygons of a multipolygon
+impl<T> From<Tin<T>> for geo_types::MultiPolygon<T>
+where
+    T: CoordFloat,
+{
+    fn from(tin: Tin<T>) -> Self {
+        MultiPolygon(
+            tin.0.into_iter().map(|t| Polygon(t.0, t.1)).collect(),
+            tin.1,
+        )
+        .into()
+    }
+}
+
+#[deprecated(since = "0.9.0", note = "use `geometry_collection.try_into()` instead")]
 pub fn try_into_geometry_collection<T>(
     geometry_collection: &GeometryCollection<T>,
 ) -> Result<geo_types::Geometry<T>, Error>
@@ -304,6 +367,13 @@ where
             Geometry::GeometryCollection(g) => {
                 geo_types::Geometry::GeometryCollection(g.try_into()?)
             }
+            Geometry::Triangle(g) => geo_types::Geometry::Triangle(g.try_into()?),
+            Geometry::PolyhedralSurface(g) => geo_types::Geometry::MultiPolygon(g.into()),
+            Geometry::Tin(g) => geo_types::Geometry::MultiPolygon(g.into()),
+            Geometry::Box(g) => geo_types::Geometry::Rect(g.into()),
+            Geometry::CircularString(_) => {
+                return Err(Error::UnsupportedGeometry("CircularString"))
+            }
         })
     }
 }
@@ -311,15 +381,19 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use types::Dimension;
 
     #[test]
     fn convert_single_item_wkt() {
-        let w_point = Point(Some(Coord {
-            x: 1.0,
-            y: 2.0,
-            z: None,
-            m: None,
-        }))
+        let w_point = Point(
+            Some(Coord {
+                x: 1.0,
+                y: 2.0,
+                z: None,
+                m: None,
+            }),
+            Dimension::XY,
+        )
         .as_item();
         let mut wkt = Wkt::new();
         wkt.add_item(w_point);
@@ -332,19 +406,25 @@ mod tests {
 
     #[test]
     fn convert_collection_wkt() {
-        let w_point_1 = Point(Some(Coord {
-            x: 1.0,
-            y: 2.0,
-            z: None,
-            m: None,
-        }))
+        let w_point_1 = Point(
+            Some(Coord {
+                x: 1.0,
+                y: 2.0,
+                z: None,
+                m: None,
+            }),
+            Dimension::XY,
+        )
         .as_item();
-        let w_point_2 = Point(Some(Coord {
-            x: 3.0,
-            y: 4.0,
-            z: None,
-            m: None,
-        }))
+        let w_point_2 = Point(
+            Some(Coord {
+                x: 3.0,
+                y: 4.0,
+                z: None,
+                m: None,
+            }),
+            Dimension::XY,
+        )
         .as_item();
         let mut wkt = Wkt::new();
         wkt.add_item(w_point_1);
@@ -365,19 +445,22 @@ mod tests {
 
     #[test]
     fn convert_empty_point() {
-        let point = Point(None);
+        let point = Point(None, Dimension::XY);
         let res: Result<geo_types::Point<f64>, Error> = point.try_into();
         assert!(res.is_err());
     }
 
     #[test]
     fn convert_point() {
-        let point = Point(Some(Coord {
-            x: 10.,
-            y: 20.,
-            z: None,
-            m: None,
-        }))
+        let point = Point(
+            Some(Coord {
+                x: 10.,
+                y: 20.,
+                z: None,
+                m: None,
+            }),
+            Dimension::XY,
+        )
         .as_item();
 
         let g_point: geo_types::Point<f64> = (10., 20.).into();
@@ -389,7 +472,7 @@ mod tests {
 
     #[test]
     fn convert_empty_linestring() {
-        let w_linestring = LineString(vec![]).as_item();
+        let w_linestring = LineString(vec![], Dimension::XY).as_item();
         let g_linestring: geo_types::LineString<f64> = geo_types::LineString(vec![]);
         assert_eq!(
             geo_types::Geometry::LineString(g_linestring),
@@ -399,20 +482,23 @@ mod tests {
 
     #[test]
     fn convert_linestring() {
-        let w_linestring = LineString(vec![
-            Coord {
-                x: 10.,
-                y: 20.,
-                z: None,
-                m: None,
-            },
-            Coord {
-                x: 30.,
-                y: 40.,
-                z: None,
-                m: None,
-            },
-        ])
+        let w_linestring = LineString(
+            vec![
+                Coord {
+                    x: 10.,
+                    y: 20.,
+                    z: None,
+                    m: None,
+                },
+                Coord {
+                    x: 30.,
+                    y: 40.,
+                    z: None,
+                    m: None,
+                },
+            ],
+            Dimension::XY,
+        )
         .as_item();
         let g_linestring: geo_types::LineString<f64> = vec![(10., 20.), (30., 40.)].into();
         assert_eq!(
@@ -423,7 +509,7 @@ mod tests {
 
     #[test]
     fn convert_empty_polygon() {
-        let w_polygon = Polygon(vec![]).as_item();
+        let w_polygon = Polygon(vec![], Dimension::XY).as_item();
         let g_polygon: geo_types::Polygon<f64> =
             geo_types::Polygon::new(geo_types::LineString(vec![]), vec![]);
         assert_eq!(
@@ -434,60 +520,69 @@ mod tests {
 
     #[test]
     fn convert_polygon() {
-        let w_polygon = Polygon(vec![
-            LineString(vec![
-                Coord {
-                    x: 0.,
-                    y: 0.,
-                    z: None,
-                    m: None,
-                },
-                Coord {
-                    x: 20.,
-                    y: 40.,
-                    z: None,
-                    m: None,
-                },
-                Coord {
-                    x: 40.,
-                    y: 0.,
-                    z: None,
-                    m: None,
-                },
-                Coord {
-                    x: 0.,
-                    y: 0.,
-                    z: None,
-                    m: None,
-                },
-            ]),
-            LineString(vec![
-                Coord {
-                    x: 5.,
-                    y: 5.,
-                    z: None,
-                    m: None,
-                },
-                Coord {
-                    x: 20.,
-                    y: 30.,
-                    z: None,
-                    m: None,
-                },
-                Coord {
-                    x: 30.,
-                    y: 5.,
-                    z: None,
-                    m: None,
-                },
-                Coord {
-                    x: 5.,
-                    y: 5.,
-                    z: None,
-                    m: None,
-                },
-            ]),
-        ])
+        let w_polygon = Polygon(
+            vec![
+                LineString(
+                    vec![
+                        Coord {
+                            x: 0.,
+                            y: 0.,
+                            z: None,
+                            m: None,
+                        },
+                        Coord {
+                            x: 20.,
+                            y: 40.,
+                            z: None,
+                            m: None,
+                        },
+                        Coord {
+                            x: 40.,
+                            y: 0.,
+                            z: None,
+                            m: None,
+                        },
+                        Coord {
+                            x: 0.,
+                            y: 0.,
+                            z: None,
+                            m: None,
+                        },
+                    ],
+                    Dimension::XY,
+                ),
+                LineString(
+                    vec![
+                        Coord {
+                            x: 5.,
+                            y: 5.,
+                            z: None,
+                            m: None,
+                        },
+                        Coord {
+                            x: 20.,
+                            y: 30.,
+                            z: None,
+                            m: None,
+                        },
+                        Coord {
+                            x: 30.,
+                            y: 5.,
+                            z: None,
+                            m: None,
+                        },
+                        Coord {
+                            x: 5.,
+                            y: 5.,
+                            z: None,
+                            m: None,
+                        },
+                    ],
+                    Dimension::XY,
+                ),
+            ],
+            Dimension::XY,
+        )
         .as_item();
         let g_polygon: geo_types::Polygon<f64> = geo_types::Polygon::new(
             vec![(0., 0.), (20., 40.), (40., 0.), (0., 0.)].into(),
@@ -501,7 +596,7 @@ mod tests {
 
     #[test]
     fn convert_empty_multilinestring() {
-        let w_multilinestring = MultiLineString(vec![]).as_item();
+        let w_multilinestring = MultiLineString(vec![], Dimension::XY).as_item();
         let g_multilinestring: geo_types::MultiLineString<f64> = geo_types::MultiLineString(vec![]);
         assert_eq!(
             geo_types::Geometry::MultiLineString(g_multilinestring),
@@ -511,36 +606,45 @@ mod tests {
 
     #[test]
     fn convert_multilinestring() {
-        let w_multilinestring = MultiLineString(vec![
-            LineString(vec![
-                Coord {
-                    x: 10.,
-                    y: 20.,
-                    z: None,
-                    m: None,
-                },
-                Coord {
-                    x: 30.,
-                    y: 40.,
-                    z: None,
-                    m: None,
-                },
-            ]),
-            LineString(vec![
-                Coord {
-                    x: 50.,
-                    y: 60.,
-                    z: None,
-                    m: None,
-                },
-                Coord {
-                    x: 70.,
-                    y: 80.,
-                    z: None,
-                    m: None,
-                },
-            ]),
-        ])
+        let w_multilinestring = MultiLineString(
+            vec![
+                LineString(
+                    vec![
+                        Coord {
+                            x: 10.,
+                            y: 20.,
+                            z: None,
+                            m: None,
+                        },
+                        Coord {
+                            x: 30.,
+                            y: 40.,
+                            z: None,
+                            m: None,
+                        },
+                    ],
+                    Dimension::XY,
+                ),
+                LineString(
+                    vec![
+                        Coord {
+                            x: 50.,
+                            y: 60.,
+                            z: None,
+                            m: None,
+                        },
+                        Coord {
+                            x: 70.,
+                            y: 80.,
+                            z: None,
+                            m: None,
+                        },
+                    ],
+                    Dimension::XY,
+                ),
+            ],
+            Dimension::XY,
+        )
         .as_item();
         let g_multilinestring: geo_types::MultiLineString<f64> = geo_types::MultiLineString(vec![
             vec![(10., 20.), (30., 40.)].into(),
@@ -554,7 +658,7 @@ mod tests {
 
     #[test]
     fn convert_empty_multipoint() {
-        let w_multipoint = MultiPoint(vec![]).as_item();
+        let w_multipoint = MultiPoint(vec![], Dimension::XY).as_item();
         let g_multipoint: geo_types::MultiPoint<f64> = geo_types::MultiPoint(vec![]);
         assert_eq!(
             geo_types::Geometry::MultiPoint(g_multipoint),
@@ -564,20 +668,29 @@ mod tests {
 
     #[test]
     fn convert_multipoint() {
-        let w_multipoint = MultiPoint(vec![
-            Point(Some(Coord {
-                x: 10.,
-                y: 20.,
-                z: None,
-                m: None,
-            })),
-            Point(Some(Coord {
-                x: 30.,
-                y: 40.,
-                z: None,
-                m: None,
-            })),
-        ])
+        let w_multipoint = MultiPoint(
+            vec![
+                Point(
+                    Some(Coord {
+                        x: 10.,
+                        y: 20.,
+                        z: None,
+                        m: None,
+                    }),
+                    Dimension::XY,
+                ),
+                Point(
+                    Some(Coord {
+                        x: 30.,
+                        y: 40.,
+                        z: None,
+                        m: None,
+                    }),
+                    Dimension::XY,
+                ),
+            ],
+            Dimension::XY,
+        )
         .as_item();
         let g_multipoint: geo_types::MultiPoint<f64> = vec![(10., 20.), (30., 40.)].into();
         assert_eq!(
@@ -588,7 +701,7 @@ mod tests {
 
     #[test]
     fn convert_empty_multipolygon() {
-        let w_multipolygon = MultiPolygon(vec![]).as_item();
+        let w_multipolygon = MultiPolygon(vec![], Dimension::XY).as_item();
         let g_multipolygon: geo_types::MultiPolygon<f64> = geo_types::MultiPolygon(vec![]);
         assert_eq!(
             geo_types::Geometry::MultiPolygon(g_multipolygon),
@@ -598,88 +711,106 @@ mod tests {
 
     #[test]
     fn convert_multipolygon() {
-        let w_multipolygon = MultiPolygon(vec![
-            Polygon(vec![
-                LineString(vec![
-                    Coord {
-                        x: 0.,
-                        y: 0.,
-                        z: None,
-                        m: None,
-                    },
-                    Coord {
-                        x: 20.,
-                        y: 40.,
-                        z: None,
-                        m: None,
-                    },
-                    Coord {
-                        x: 40.,
-                        y: 0.,
-                        z: None,
-                        m: None,
-                    },
-                    Coord {
-                        x: 0.,
-                        y: 0.,
-                        z: None,
-                        m: None,
-                    },
-                ]),
-                LineString(vec![
-                    Coord {
-                        x: 5.,
-                        y: 5.,
-                        z: None,
-                        m: None,
-                    },
-                    Coord {
-                        x: 20.,
-                        y: 30.,
-                        z: None,
-                        m: None,
-                    },
-                    Coord {
-                        x: 30.,
-                        y: 5.,
-                        z: None,
-                        m: None,
-                    },
-                    Coord {
-                        x: 5.,
-                        y: 5.,
-                        z: None,
-                        m: None,
-                    },
-                ]),
-            ]),
-            Polygon(vec![LineString(vec![
-                Coord {
-                    x: 40.,
-                    y: 40.,
-                    z: None,
-                    m: None,
-                },
-                Coord {
-                    x: 20.,
-                    y: 45.,
-                    z: None,
-                    m: None,
-                },
-                Coord {
-                    x: 45.,
-                    y: 30.,
-                    z: None,
-                    m: None,
-                },
-                Coord {
-                    x: 40.,
-                    y: 40.,
-                    z: None,
-                    m: None,
-                },
-            ])]),
-        ])
+        let w_multipolygon = MultiPolygon(
+            vec![
+                Polygon(
+                    vec![
+                        LineString(
+                            vec![
+                                Coord {
+                                    x: 0.,
+                                    y: 0.,
+                                    z: None,
+                                    m: None,
+                                },
+                                Coord {
+                                    x: 20.,
+                                    y: 40.,
+                                    z: None,
+                                    m: None,
+                                },
+                                Coord {
+                                    x: 40.,
+                                    y: 0.,
+                                    z: None,
+                                    m: None,
+                                },
+                                Coord {
+                                    x: 0.,
+                                    y: 0.,
+                                    z: None,
+                                    m: None,
+                                },
+                            ],
+                            Dimension::XY,
+                        ),
+                        LineString(
+                            vec![
+                                Coord {
+                                    x: 5.,
+                                    y: 5.,
+                                    z: None,
+                                    m: None,
+                                },
+                                Coord {
+                                    x: 20.,
+                                    y: 30.,
+                                    z: None,
+                                    m: None,
+                                },
+                                Coord {
+                                    x: 30.,
+                                    y: 5.,
+                                    z: None,
+                                    m: None,
+                                },
+                                Coord {
+                                    x: 5.,
+                                    y: 5.,
+                                    z: None,
+                                    m: None,
+                                },
+                            ],
+                            Dimension::XY,
+                        ),
+                    ],
+                    Dimension::XY,
+                ),
+                Polygon(
+                    vec![LineString(
+                        vec![
+                            Coord {
+                                x: 40.,
+                                y: 40.,
+                                z: None,
+                                m: None,
+                            },
+                            Coord {
+                                x: 20.,
+                                y: 45.,
+                                z: None,
+                                m: None,
+                            },
+                            Coord {
+                                x: 45.,
+                                y: 30.,
+                                z: None,
+                                m: None,
+                            },
+                            Coord {
+                                x: 40.,
+                                y: 40.,
+                                z: None,
+                                m: None,
+                            },
+                        ],
+                        Dimension::XY,
+                    )],
+                    Dimension::XY,
+                ),
+            ],
+            Dimension::XY,
+        )
         .as_item();
 
         let g_multipolygon: geo_types::MultiPolygon<f64> = geo_types::MultiPolygon(vec![
@@ -698,9 +829,53 @@ mod tests {
         );
     }
 
+    #[test]
+    fn convert_empty_triangle() {
+        let triangle: Triangle<f64> = Triangle(vec![], Dimension::XY);
+        let res: Result<geo_types::Triangle<f64>, Error> = triangle.try_into();
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn convert_triangle() {
+        let wkt: Wkt<f64> = Wkt::from_str("TRIANGLE ((0 0, 0 9, 9 0, 0 0))").unwrap();
+        let g_triangle = geo_types::Triangle::new(
+            geo_types::coord! { x: 0., y: 0. },
+            geo_types::coord! { x: 0., y: 9. },
+            geo_types::coord! { x: 9., y: 0. },
+        );
+        assert_eq!(
+            geo_types::Geometry::Triangle(g_triangle),
+            wkt.try_into().unwrap()
+        );
+    }
+
+    #[test]
+    fn convert_tin() {
+        let wkt: Wkt<f64> = Wkt::from_str("TIN (((0 0, 0 9, 9 0, 0 0)))").unwrap();
+        let g_multipolygon = geo_types::MultiPolygon(vec![geo_types::Polygon::new(
+            geo_types::LineString::from(vec![(0., 0.), (0., 9.), (9., 0.), (0., 0.)]),
+            vec![],
+        )]);
+        assert_eq!(
+            geo_types::Geometry::MultiPolygon(g_multipolygon),
+            wkt.try_into().unwrap()
+        );
+    }
+
+    #[test]
+    fn convert_circularstring() {
+        let wkt: Wkt<f64> = Wkt::from_str("CIRCULARSTRING (0 0, 1 1, 2 0)").unwrap();
+        let res: Result<geo_types::Geometry<f64>, Error> = wkt.try_into();
+        assert!(matches!(
+            res,
+            Err(Error::UnsupportedGeometry("CircularString"))
+        ));
+    }
+
     #[test]
     fn convert_empty_geometrycollection() {
-        let w_geometrycollection = GeometryCollection(vec![]).as_item();
+        let w_geometrycollection = GeometryCollection(vec![], Dimension::XY).as_item();
         let g_geometrycollection: geo_types::GeometryCollection<f64> =
             geo_types::GeometryCollection(vec![]);
         assert_eq!(
@@ -711,60 +886,19 @@ mod tests {
 
     #[test]
     fn convert_geometrycollection() {
-        let w_point = Point(Some(Coord {
-            x: 10.,
-            y: 20.,
-            z: None,
-            m: None,
-        }))
-        .as_item();
-
-        let w_linestring = LineString(vec![
-            Coord {
+        let w_point = Point(
+            Some(Coord {
                 x: 10.,
                 y: 20.,
                 z: None,
                 m: None,
-            },
-            Coord {
-                x: 30.,
-                y: 40.,
-                z: None,
-                m: None,
-            },
-        ])
+            }),
+            Dimension::XY,
+        )
         .as_item();
 
-        let w_polygon = Polygon(vec![LineString(vec![
-            Coord {
-                x: 0.,
-                y: 0.,
-                z: None,
-                m: None,
-            },
-            Coord {
-                x: 20.,
-                y: 40.,
-                z: None,
-                m: None,
-            },
-            Coord {
-                x: 40.,
-                y: 0.,
-                z: None,
-                m: None,
-            },
-            Coord {
-                x: 0.,
-                y: 0.,
-                z: None,
-                m: None,
-            },
-        ])])
-        .as_item();
-
-        let w_multilinestring = MultiLineString(vec![
-            LineString(vec![
+        let w_linestring = LineString(
+            vec![
                 Coord {
                     x: 10.,
                     y: 20.,
@@ -777,104 +911,193 @@ mod tests {
                     z: None,
                     m: None,
                 },
-            ]),
-            LineString(vec![
-                Coord {
-                    x: 50.,
-                    y: 60.,
-                    z: None,
-                    m: None,
-                },
-                Coord {
-                    x: 70.,
-                    y: 80.,
-                    z: None,
-                    m: None,
-                },
-            ]),
-        ])
+            ],
+            Dimension::XY,
+        )
         .as_item();
 
-        let w_multipoint = MultiPoint(vec![
-            Point(Some(Coord {
-                x: 10.,
-                y: 20.,
-                z: None,
-                m: None,
-            })),
-            Point(Some(Coord {
-                x: 30.,
-                y: 40.,
-                z: None,
-                m: None,
-            })),
-        ])
+        let w_polygon = Polygon(
+            vec![LineString(
+                vec![
+                    Coord {
+                        x: 0.,
+                        y: 0.,
+                        z: None,
+                        m: None,
+                    },
+                    Coord {
+                        x: 20.,
+                        y: 40.,
+                        z: None,
+                        m: None,
+                    },
+                    Coord {
+                        x: 40.,
+                        y: 0.,
+                        z: None,
+                        m: None,
+                    },
+                    Coord {
+                        x: 0.,
+                        y: 0.,
+                        z: None,
+                        m: None,
+                    },
+                ],
+                Dimension::XY,
+            )],
+            Dimension::XY,
+        )
         .as_item();
 
-        let w_multipolygon = MultiPolygon(vec![
-            Polygon(vec![LineString(vec![
-                Coord {
-                    x: 0.,
-                    y: 0.,
-                    z: None,
-                    m: None,
-                },
-                Coord {
-                    x: 20.,
-                    y: 40.,
-                    z: None,
-                    m: None,
-                },
-                Coord {
-                    x: 40.,
-                    y: 0.,
-                    z: None,
-                    m: None,
-                },
-                Coord {
-                    x: 0.,
-                    y: 0.,
-                    z: None,
-                    m: None,
-                },
-            ])]),
-            Polygon(vec![LineString(vec![
-                Coord {
-                    x: 40.,
-                    y: 40.,
-                    z: None,
-                    m: None,
-                },
-                Coord {
-                    x: 20.,
-                    y: 45.,
-                    z: None,
-                    m: None,
-                },
-                Coord {
-                    x: 45.,
-                    y: 30.,
-                    z: None,
-                    m: None,
-                },
-                Coord {
-                    x: 40.,
-                    y: 40.,
-                    z: None,
-                    m: None,
-                },
-            ])]),
-        ])
+        let w_multilinestring = MultiLineString(
+            vec![
+                LineString(
+                    vec![
+                        Coord {
+                            x: 10.,
+                            y: 20.,
+                            z: None,
+                            m: None,
+                        },
+                        Coord {
+                            x: 30.,
+                            y: 40.,
+                            z: None,
+                            m: None,
+                        },
+                    ],
+                    Dimension::XY,
+                ),
+                LineString(
+                    vec![
+                        Coord {
+                            x: 50.,
+                            y: 60.,
+                            z: None,
+                            m: None,
+                        },
+                        Coord {
+                            x: 70.,
+                            y: 80.,
+                            z: None,
+                            m: None,
+                        },
+                    ],
+                    Dimension::XY,
+                ),
+            ],
+            Dimension::XY,
+        )
+        .as_item();
+
+        let w_multipoint = MultiPoint(
+            vec![
+                Point(
+                    Some(Coord {
+                        x: 10.,
+                        y: 20.,
+                        z: None,
+                        m: None,
+                    }),
+                    Dimension::XY,
+                ),
+                Point(
+                    Some(Coord {
+                        x: 30.,
+                        y: 40.,
+                        z: None,
+                        m: None,
+                    }),
+                    Dimension::XY,
+                ),
+            ],
+            Dimension::XY,
+        )
+        .as_item();
+
+        let w_multipolygon = MultiPolygon(
+            vec![
+                Polygon(
+                    vec![LineString(
+                        vec![
+                            Coord {
+                                x: 0.,
+                                y: 0.,
+                                z: None,
+                                m: None,
+                            },
+                            Coord {
+                                x: 20.,
+                                y: 40.,
+                                z: None,
+                                m: None,
+                            },
+                            Coord {
+                                x: 40.,
+                                y: 0.,
+                                z: None,
+                                m: None,
+                            },
+                            Coord {
+                                x: 0.,
+                                y: 0.,
+                                z: None,
+                                m: None,
+                            },
+                        ],
+                        Dimension::XY,
+                    )],
+                    Dimension::XY,
+                ),
+                Polygon(
+                    vec![LineString(
+                        vec![
+                            Coord {
+                                x: 40.,
+                                y: 40.,
+                                z: None,
+                                m: None,
+                            },
+                            Coord {
+                                x: 20.,
+                                y: 45.,
+                                z: None,
+                                m: None,
+                            },
+                            Coord {
+                                x: 45.,
+                                y: 30.,
+                                z: None,
+                                m: None,
+                            },
+                            Coord {
+                                x: 40.,
+                                y: 40.,
+                                z: None,
+                                m: None,
+                            },
+                        ],
+                        Dimension::XY,
+                    )],
+                    Dimension::XY,
+                ),
+            ],
+            Dimension::XY,
+        )
         .as_item();
 
-        let w_geometrycollection = GeometryCollection(vec![
-            w_point,
-            w_multipoint,
-            w_linestring,
-            w_multilinestring,
-            w_polygon,
-            w_multipolygon,
-        ])
+        let w_geometrycollection = GeometryCollection(
+            vec![
+                w_point,
+                w_multipoint,
+                w_linestring,
+                w_multilinestring,
+                w_polygon,
+                w_multipolygon,
+            ],
+            Dimension::XY,
+        )
         .as_item();
 
         let g_point: geo_types::Point<f64> = (10., 20.).into();
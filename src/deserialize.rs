@@ -1,11 +1,11 @@
 use crate::{Geometry, Wkt, WktFloat};
-use serde::de::{Deserializer, Error, Visitor};
-use std::{
+use core::{
     default::Default,
     fmt::{self, Debug},
     marker::PhantomData,
     str::FromStr,
 };
+use serde::de::{Deserializer, Error, Visitor};
 
 struct WktVisitor<T> {
     _marker: PhantomData<T>,
@@ -14,7 +14,7 @@ struct WktVisitor<T> {
 impl<T> Default for WktVisitor<T> {
     fn default() -> Self {
         WktVisitor {
-            _marker: PhantomData::default(),
+            _marker: PhantomData,
         }
     }
 }
@@ -54,7 +54,7 @@ struct GeometryVisitor<T> {
 impl<T> Default for GeometryVisitor<T> {
     fn default() -> Self {
         GeometryVisitor {
-            _marker: PhantomData::default(),
+            _marker: PhantomData,
         }
     }
 }
@@ -119,8 +119,8 @@ where
 {
     use serde::Deserialize;
     Geometry::deserialize(deserializer).and_then(|g: Geometry<T>| {
-        use std::convert::TryInto;
-        g.try_into().map_err(|e| D::Error::custom(e))
+        use core::convert::TryInto;
+        g.try_into().map_err(D::Error::custom)
     })
 }
 
@@ -159,14 +159,14 @@ where
 {
     use serde::Deserialize;
     Wkt::deserialize(deserializer).and_then(|wkt: Wkt<T>| {
-        use std::convert::TryFrom;
+        use core::convert::TryFrom;
         geo_types::Geometry::try_from(wkt)
             .map_err(D::Error::custom)
             .and_then(|geom| {
                 use geo_types::Geometry::*;
                 match geom {
                     Point(p) => Ok(Some(p)),
-                    MultiPoint(mp) if mp.0.len() == 0 => Ok(None),
+                    MultiPoint(mp) if mp.0.is_empty() => Ok(None),
                     _ => geo_types::Point::try_from(geom)
                         .map(Some)
                         .map_err(D::Error::custom),
@@ -186,6 +186,7 @@ mod tests {
         value::{Error, StrDeserializer},
         Deserializer, Error as _, IntoDeserializer,
     };
+    use types::Dimension;
 
     mod wkt {
         use super::*;
@@ -198,12 +199,15 @@ mod tests {
                 .unwrap();
             assert!(matches!(
                 wkt.items[0],
-                Geometry::Point(Point(Some(Coord {
-                    x: _, // floating-point types cannot be used in patterns
-                    y: _, // floating-point types cannot be used in patterns
-                    z: None,
-                    m: None,
-                })))
+                Geometry::Point(Point(
+                    Some(Coord {
+                        x: _, // floating-point types cannot be used in patterns
+                        y: _, // floating-point types cannot be used in patterns
+                        z: None,
+                        m: None,
+                    }),
+                    Dimension::XY
+                ))
             ));
         }
 
@@ -213,7 +217,7 @@ mod tests {
             let wkt = deserializer.deserialize_any(WktVisitor::<f64>::default());
             assert_eq!(
                 wkt.unwrap_err(),
-                Error::custom("Expected a number for the Y coordinate")
+                Error::custom("Malformed number \"20.1A\"")
             );
         }
     }
@@ -229,12 +233,15 @@ mod tests {
                 .unwrap();
             assert!(matches!(
                 geometry,
-                Geometry::Point(Point(Some(Coord {
-                    x: _, // floating-point types cannot be used in patterns
-                    y: _, // floating-point types cannot be used in patterns
-                    z: None,
-                    m: None,
-                })))
+                Geometry::Point(Point(
+                    Some(Coord {
+                        x: _, // floating-point types cannot be used in patterns
+                        y: _, // floating-point types cannot be used in patterns
+                        z: None,
+                        m: None,
+                    }),
+                    Dimension::XY
+                ))
             ));
         }
 
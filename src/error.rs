@@ -0,0 +1,110 @@
+// Copyright 2014-2021 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::string::String;
+use thiserror::Error;
+use types::{Dimension, GeometryType};
+
+/// Errors which can occur when parsing WKT
+#[derive(Error, Clone, Debug, PartialEq)]
+pub enum WktError {
+    /// The input does not follow the WKT grammar
+    #[error("{0}")]
+    Syntax(&'static str),
+    /// A token starting like a number cannot be read as one, such as `4.2p`
+    #[error("Malformed number {0:?}")]
+    MalformedNumber(String),
+    /// A coordinate is NaN or infinite
+    #[error("Invalid number {0:?}: coordinates must be finite")]
+    InvalidNumber(String),
+    /// An ordinate cannot be represented by the requested integer type
+    #[error("Ordinate {0} is not an integer in range")]
+    NotAnInteger(String),
+    /// A polygon ring does not end on its starting coordinate
+    #[error("Polygon ring is not closed")]
+    UnclosedRing,
+    /// A geometry has fewer coordinates than the specification allows
+    #[error("{geometry} requires at least {min} coordinates, found {found}")]
+    TooFewCoordinates {
+        geometry: &'static str,
+        min: usize,
+        found: usize,
+    },
+    /// A geometry has a different number of coordinates than the specification requires
+    #[error("{geometry} requires exactly {expected} coordinates, found {found}")]
+    WrongNumberOfCoordinates {
+        geometry: &'static str,
+        expected: usize,
+        found: usize,
+    },
+    /// Coordinates of a single geometry do not share one dimension
+    #[error("Inconsistent dimension: expected {expected:?} coordinates, found {found:?}")]
+    InconsistentDimension {
+        expected: Dimension,
+        found: Dimension,
+    },
+    /// Items of a `Wkt` expected to share a type do not
+    #[error("Heterogeneous items: expected {expected:?}, found {found:?}")]
+    HeterogeneousCollection {
+        expected: GeometryType,
+        found: GeometryType,
+    },
+    /// A geometry of one type was expected, as by `Polygon::from_wkt_str`, but another was
+    /// found
+    #[error("Expected a {expected:?}, found a {found:?}")]
+    UnexpectedType {
+        expected: GeometryType,
+        found: GeometryType,
+    },
+    /// A `Wkt` has no items where at least one is required
+    #[error("No items")]
+    NoItems,
+    /// Geometry collections are nested beyond the parser's maximum depth
+    #[error("Geometry collections are nested too deeply")]
+    MaxDepthExceeded,
+    /// The input contains a non-ASCII character outside of comments, at the given byte
+    /// offset
+    #[error("Unexpected non-ASCII character {character:?} at byte {position}")]
+    NonAscii { character: char, position: usize },
+    /// A GeoJSON value is not a valid geometry object
+    #[error("Invalid GeoJSON geometry: {0}")]
+    InvalidGeoJson(&'static str),
+    /// Bytes passed to `Wkt::from_cache_bytes` are not a valid cache dump
+    #[error("Invalid cache bytes: {0}")]
+    InvalidCache(&'static str),
+    /// Bytes passed to `Geometry::from_ewkb` are not valid WKB
+    #[error("Invalid WKB: {0}")]
+    InvalidWkb(&'static str),
+    /// A line read by `WktParser::parse_lines` is longer than the configured maximum, given
+    /// with its one-based line number
+    #[error("Line {line} is longer than {max} bytes")]
+    LineTooLong { line: usize, max: usize },
+    /// The input is the WKT definition of a coordinate reference system, such as
+    /// `GEOGCS[...]`, rather than a geometry
+    #[error("CRS WKT is not supported, expected a geometry")]
+    CrsWktNotSupported,
+    /// A coordinate lies outside the envelope given to `Wkt::from_str_with_bounds`, given
+    /// with its index in document order
+    #[error("Coordinate {index} is out of bounds")]
+    CoordinateOutOfBounds { index: usize },
+    /// A comma separated list has an empty element, as in `LINESTRING (1 2,, 3 4)`
+    #[error("Empty coordinate in a comma separated list")]
+    EmptyCoordinate,
+    /// A point cannot be encoded as a geohash
+    #[error("Cannot encode geohash: {0}")]
+    InvalidGeohash(&'static str),
+    /// Reading the input failed
+    #[error("I/O error: {0}")]
+    Io(String),
+}
@@ -0,0 +1,120 @@
+// Copyright 2014-2021 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::str::FromStr;
+use types::{
+    CircularString, LineString, MultiLineString, MultiPolygon, Polygon, PolyhedralSurface, Triangle,
+};
+use {Geometry, Wkt, WktFloat};
+
+/// The optional WKT features used by the items of a [`Wkt`], see [`Wkt::features_used`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ParsedFeatures {
+    /// A geometry is tagged `Z` or `ZM`, or a coordinate has a `z`
+    pub has_z: bool,
+    /// A geometry is tagged `M` or `ZM`, or a coordinate has an `m`
+    pub has_m: bool,
+    /// A geometry, or a part of one, is `EMPTY`
+    pub has_empty: bool,
+}
+
+impl<T> Wkt<T>
+where
+    T: WktFloat + FromStr + Default,
+{
+    /// Reports which optional features the items use, for example to choose a storage
+    /// schema. The members of collections are inspected as well.
+    pub fn features_used(&self) -> ParsedFeatures {
+        let mut features = ParsedFeatures::default();
+        for item in &self.items {
+            features.add(item);
+        }
+        features
+    }
+}
+
+impl ParsedFeatures {
+    fn add<T: WktFloat>(&mut self, geometry: &Geometry<T>) {
+        let dim = geometry.tagged_dimension();
+        self.has_z |= dim.has_z();
+        self.has_m |= dim.has_m();
+        if let Geometry::GeometryCollection(collection) = geometry {
+            self.has_empty |= collection.0.is_empty();
+            collection.0.iter().for_each(|item| self.add(item));
+            return;
+        }
+        for coord in geometry.coords() {
+            self.has_z |= coord.z.is_some();
+            self.has_m |= coord.m.is_some();
+        }
+        self.has_empty |= has_empty(geometry);
+    }
+}
+
+fn has_empty<T: WktFloat>(geometry: &Geometry<T>) -> bool {
+    let empty_rings =
+        |rings: &[LineString<T>]| rings.is_empty() || rings.iter().any(|l| l.0.is_empty());
+    let empty_polygons =
+        |polygons: &[Polygon<T>]| polygons.is_empty() || polygons.iter().any(|p| empty_rings(&p.0));
+    match geometry {
+        Geometry::Point(point) => point.0.is_none(),
+        Geometry::Box(_) => false,
+        Geometry::LineString(LineString(coords, _))
+        | Geometry::CircularString(CircularString(coords, _)) => coords.is_empty(),
+        Geometry::Polygon(Polygon(rings, _))
+        | Geometry::Triangle(Triangle(rings, _))
+        | Geometry::MultiLineString(MultiLineString(rings, _)) => empty_rings(rings),
+        Geometry::MultiPoint(multipoint) => {
+            multipoint.0.is_empty() || multipoint.0.iter().any(|point| point.0.is_none())
+        }
+        Geometry::MultiPolygon(MultiPolygon(polygons, _))
+        | Geometry::PolyhedralSurface(PolyhedralSurface(polygons, _)) => empty_polygons(polygons),
+        Geometry::Tin(tin) => tin.0.is_empty() || tin.0.iter().any(|t| empty_rings(&t.0)),
+        Geometry::GeometryCollection(collection) => collection.0.iter().any(has_empty),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ParsedFeatures;
+    use Wkt;
+
+    #[test]
+    fn mixed_collection_features() {
+        let wkt: Wkt<f64> =
+            Wkt::from_str("GEOMETRYCOLLECTION (LINESTRING Z (0 0 1, 1 1 2), POINT (3 4))").unwrap();
+        assert_eq!(
+            ParsedFeatures {
+                has_z: true,
+                has_m: false,
+                has_empty: false,
+            },
+            wkt.features_used()
+        );
+    }
+
+    #[test]
+    fn empty_features() {
+        let wkt: Wkt<f64> = Wkt::from_str("GEOMETRYCOLLECTION (POINT M EMPTY)").unwrap();
+        assert_eq!(
+            ParsedFeatures {
+                has_z: false,
+                has_m: true,
+                has_empty: true,
+            },
+            wkt.features_used()
+        );
+        assert_eq!(ParsedFeatures::default(), <Wkt<f64>>::new().features_used());
+    }
+}
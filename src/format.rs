@@ -0,0 +1,140 @@
+// Copyright 2014-2021 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+use types::Dimension;
+use WktFloat;
+
+/// The letter case of keywords in WKT output.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum KeywordCase {
+    /// `POINT Z (1 2 3)`, as written by `Display`
+    #[default]
+    Upper,
+    /// `point z (1 2 3)`
+    Lower,
+}
+
+/// Applies `case` to the keywords of uppercase WKT `wkt`, leaving numbers such as `NaN`
+/// untouched.
+pub fn apply_keyword_case(wkt: String, case: KeywordCase) -> String {
+    match case {
+        KeywordCase::Upper => wkt,
+        KeywordCase::Lower => {
+            let mut out = String::with_capacity(wkt.len());
+            let mut rest = wkt.as_str();
+            while let Some(start) = rest.find(|c: char| c.is_ascii_alphabetic()) {
+                out.push_str(&rest[..start]);
+                rest = &rest[start..];
+                let end = rest
+                    .find(|c: char| !c.is_ascii_alphanumeric())
+                    .unwrap_or(rest.len());
+                match &rest[..end] {
+                    "NaN" => out.push_str("NaN"),
+                    word => out.push_str(&word.to_ascii_lowercase()),
+                }
+                rest = &rest[end..];
+            }
+            out.push_str(rest);
+            out
+        }
+    }
+}
+
+/// Writes an empty geometry, keeping its dimension tag as in `POINT Z EMPTY`.
+pub fn write_empty(f: &mut fmt::Formatter, keyword: &str, dim: Dimension) -> fmt::Result {
+    match dim {
+        Dimension::XY => write!(f, "{} EMPTY", keyword),
+        dim => write!(f, "{} {} EMPTY", keyword, dim.tag()),
+    }
+}
+
+/// Rounds `value` half away from zero to `figs` significant figures.
+///
+/// The rounding is applied to the shortest decimal representation of `value` rather than
+/// to its exact binary value, so `0.0012345` becomes `0.001235` as written.
+pub fn round_sig_figs<T>(value: T, figs: usize) -> T
+where
+    T: WktFloat + fmt::LowerExp + FromStr,
+{
+    let figs = figs.max(1);
+    if !value.is_finite() || value.is_zero() {
+        return value;
+    }
+
+    let formatted = format!("{:e}", value.abs());
+    let (mantissa, exponent) = match formatted.find('e') {
+        Some(i) => (&formatted[..i], &formatted[i + 1..]),
+        None => return value,
+    };
+    let mut exponent: i32 = match exponent.parse() {
+        Ok(exponent) => exponent,
+        Err(_) => return value,
+    };
+    let mut digits: Vec<u8> = mantissa.bytes().filter(|b| *b != b'.').collect();
+    if digits.len() <= figs {
+        return value;
+    }
+
+    let round_up = digits[figs] >= b'5';
+    digits.truncate(figs);
+    if round_up {
+        let mut carry = true;
+        for digit in digits.iter_mut().rev() {
+            if *digit == b'9' {
+                *digit = b'0';
+            } else {
+                *digit += 1;
+                carry = false;
+                break;
+            }
+        }
+        if carry {
+            digits.insert(0, b'1');
+            digits.pop();
+            exponent += 1;
+        }
+    }
+
+    let digits = String::from_utf8(digits).expect("digits are ASCII");
+    let rounded = if digits.len() == 1 {
+        format!("{}e{}", digits, exponent)
+    } else {
+        format!("{}.{}e{}", &digits[..1], &digits[1..], exponent)
+    };
+    match rounded.parse::<T>() {
+        Ok(rounded) if value.is_sign_negative() => -rounded,
+        Ok(rounded) => rounded,
+        Err(_) => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::round_sig_figs;
+
+    #[test]
+    fn sig_figs() {
+        assert_eq!(1235., round_sig_figs(1234.5678, 4));
+        assert_eq!(0.001235, round_sig_figs(0.0012345, 4));
+        assert_eq!(-0.001235, round_sig_figs(-0.0012345, 4));
+        assert_eq!(1000., round_sig_figs(999.96, 4));
+        assert_eq!(1.5, round_sig_figs(1.5, 4));
+        assert_eq!(2., round_sig_figs(1.5, 1));
+        assert_eq!(0., round_sig_figs(0., 3));
+    }
+}
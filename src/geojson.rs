@@ -0,0 +1,345 @@
+// Copyright 2014-2021 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+#[cfg(feature = "geojson")]
+use serde_json::Value;
+use types::*;
+#[cfg(feature = "geojson")]
+use WktError;
+use {Geometry, WktFloat};
+
+impl<T> Geometry<T>
+where
+    T: WktFloat + fmt::Display,
+{
+    /// Formats the geometry as a GeoJSON geometry object.
+    ///
+    /// Positions carry `z` as a third element, while `m` has no GeoJSON equivalent and is
+    /// dropped. Types GeoJSON lacks are written as their closest match: a `Triangle` as a
+    /// `Polygon`, a `PolyhedralSurface` or `Tin` as a `MultiPolygon`, a `CircularString` as
+    /// a `LineString` through its control points and a `BOX` as its rectangle `Polygon`.
+    /// JSON has no `NaN` or infinity, so non-finite ordinates are written as `null`.
+    pub fn to_geojson(&self) -> String {
+        match self {
+            Geometry::Point(point) => {
+                let position = point.0.as_ref().map(position).unwrap_or_default();
+                object("Point", &format!("[{}]", position))
+            }
+            Geometry::LineString(LineString(coords, _))
+            | Geometry::CircularString(CircularString(coords, _)) => {
+                object("LineString", &line(coords))
+            }
+            Geometry::Polygon(Polygon(rings, _)) | Geometry::Triangle(Triangle(rings, _)) => {
+                object("Polygon", &lines(rings))
+            }
+            Geometry::Box(bbox) => object("Polygon", &lines(&bbox.to_polygon().0)),
+            Geometry::MultiPoint(multipoint) => {
+                let points = multipoint
+                    .0
+                    .iter()
+                    .filter_map(|p| p.0.as_ref())
+                    .map(|c| format!("[{}]", position(c)))
+                    .collect::<Vec<_>>();
+                object("MultiPoint", &format!("[{}]", points.join(",")))
+            }
+            Geometry::MultiLineString(multilinestring) => {
+                object("MultiLineString", &lines(&multilinestring.0))
+            }
+            Geometry::MultiPolygon(MultiPolygon(polygons, _))
+            | Geometry::PolyhedralSurface(PolyhedralSurface(polygons, _)) => {
+                let polygons = polygons.iter().map(|p| lines(&p.0)).collect::<Vec<_>>();
+                object("MultiPolygon", &format!("[{}]", polygons.join(",")))
+            }
+            Geometry::Tin(tin) => {
+                let polygons = tin.0.iter().map(|t| lines(&t.0)).collect::<Vec<_>>();
+                object("MultiPolygon", &format!("[{}]", polygons.join(",")))
+            }
+            Geometry::GeometryCollection(collection) => {
+                let geometries = collection
+                    .0
+                    .iter()
+                    .map(Geometry::to_geojson)
+                    .collect::<Vec<_>>();
+                format!(
+                    r#"{{"type":"GeometryCollection","geometries":[{}]}}"#,
+                    geometries.join(",")
+                )
+            }
+        }
+    }
+}
+
+#[cfg(feature = "geojson")]
+impl<T> Geometry<T>
+where
+    T: WktFloat,
+{
+    /// Reads a GeoJSON geometry object, the inverse of [`Geometry::to_geojson`]. Positions
+    /// with a third element are read as `z`, and must all agree on their dimension.
+    /// `Feature` and `FeatureCollection` wrappers are rejected.
+    pub fn from_geojson(value: &Value) -> Result<Self, WktError> {
+        let kind = match value.get("type").and_then(Value::as_str) {
+            Some(kind) => kind,
+            None => return Err(WktError::InvalidGeoJson("missing type")),
+        };
+        let mut geometry = match kind {
+            "GeometryCollection" => {
+                let geometries = match value.get("geometries").and_then(Value::as_array) {
+                    Some(geometries) => geometries,
+                    None => return Err(WktError::InvalidGeoJson("missing geometries")),
+                };
+                Geometry::GeometryCollection(GeometryCollection(
+                    geometries
+                        .iter()
+                        .map(Geometry::from_geojson)
+                        .collect::<Result<_, _>>()?,
+                    Dimension::XY,
+                ))
+            }
+            "Feature" | "FeatureCollection" => {
+                return Err(WktError::InvalidGeoJson(
+                    "expected a geometry, not a feature",
+                ))
+            }
+            _ => {
+                let coordinates = match value.get("coordinates") {
+                    Some(coordinates) => coordinates,
+                    None => return Err(WktError::InvalidGeoJson("missing coordinates")),
+                };
+                geometry_from_coordinates(kind, coordinates)?
+            }
+        };
+        let dim = geometry.dimension()?;
+        geometry.set_dimension(dim);
+        Ok(geometry)
+    }
+}
+
+#[cfg(feature = "geojson")]
+fn geometry_from_coordinates<T: WktFloat>(
+    kind: &str,
+    coordinates: &Value,
+) -> Result<Geometry<T>, WktError> {
+    Ok(match kind {
+        "Point" if array(coordinates)?.is_empty() => Geometry::Point(Point(None, Dimension::XY)),
+        "Point" => Geometry::Point(Point(Some(read_position(coordinates)?), Dimension::XY)),
+        "LineString" => {
+            Geometry::LineString(LineString(read_positions(coordinates)?, Dimension::XY))
+        }
+        "Polygon" => Geometry::Polygon(Polygon(read_lines(coordinates)?, Dimension::XY)),
+        "MultiPoint" => Geometry::MultiPoint(MultiPoint(
+            read_positions(coordinates)?
+                .into_iter()
+                .map(|c| Point(Some(c), Dimension::XY))
+                .collect(),
+            Dimension::XY,
+        )),
+        "MultiLineString" => {
+            Geometry::MultiLineString(MultiLineString(read_lines(coordinates)?, Dimension::XY))
+        }
+        "MultiPolygon" => Geometry::MultiPolygon(MultiPolygon(
+            array(coordinates)?
+                .iter()
+                .map(|p| read_lines(p).map(|rings| Polygon(rings, Dimension::XY)))
+                .collect::<Result<_, _>>()?,
+            Dimension::XY,
+        )),
+        _ => return Err(WktError::InvalidGeoJson("unknown geometry type")),
+    })
+}
+
+#[cfg(feature = "geojson")]
+fn array(value: &Value) -> Result<&Vec<Value>, WktError> {
+    value
+        .as_array()
+        .ok_or(WktError::InvalidGeoJson("expected an array"))
+}
+
+#[cfg(feature = "geojson")]
+fn read_position<T: WktFloat>(value: &Value) -> Result<Coord<T>, WktError> {
+    let ordinates = array(value)?
+        .iter()
+        .map(|v| v.as_f64().and_then(T::from))
+        .collect::<Option<Vec<T>>>()
+        .ok_or(WktError::InvalidGeoJson("expected numeric ordinates"))?;
+    match ordinates[..] {
+        [x, y] => Ok(Coord {
+            x,
+            y,
+            z: None,
+            m: None,
+        }),
+        [x, y, z] => Ok(Coord {
+            x,
+            y,
+            z: Some(z),
+            m: None,
+        }),
+        _ => Err(WktError::InvalidGeoJson(
+            "positions must have two or three elements",
+        )),
+    }
+}
+
+#[cfg(feature = "geojson")]
+fn read_positions<T: WktFloat>(value: &Value) -> Result<Vec<Coord<T>>, WktError> {
+    array(value)?.iter().map(read_position).collect()
+}
+
+#[cfg(feature = "geojson")]
+fn read_lines<T: WktFloat>(value: &Value) -> Result<Vec<LineString<T>>, WktError> {
+    array(value)?
+        .iter()
+        .map(|l| read_positions(l).map(|coords| LineString(coords, Dimension::XY)))
+        .collect()
+}
+
+fn object(kind: &str, coordinates: &str) -> String {
+    format!(r#"{{"type":"{}","coordinates":{}}}"#, kind, coordinates)
+}
+
+fn ordinate<T: WktFloat + fmt::Display>(value: T) -> String {
+    if value.is_finite() {
+        format!("{}", value)
+    } else {
+        String::from("null")
+    }
+}
+
+fn position<T: WktFloat + fmt::Display>(coord: &Coord<T>) -> String {
+    let (x, y) = (ordinate(coord.x), ordinate(coord.y));
+    match coord.z {
+        Some(z) => format!("{},{},{}", x, y, ordinate(z)),
+        None => format!("{},{}", x, y),
+    }
+}
+
+fn line<T: WktFloat + fmt::Display>(coords: &[Coord<T>]) -> String {
+    let positions = coords
+        .iter()
+        .map(|c| format!("[{}]", position(c)))
+        .collect::<Vec<_>>();
+    format!("[{}]", positions.join(","))
+}
+
+fn lines<T: WktFloat + fmt::Display>(lines: &[LineString<T>]) -> String {
+    let lines = lines.iter().map(|l| line(&l.0)).collect::<Vec<_>>();
+    format!("[{}]", lines.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "geojson")]
+    use types::Dimension;
+    #[cfg(feature = "geojson")]
+    use {Geometry, WktError};
+    use {Wkt, WktParser};
+
+    fn geojson(wkt: &str) -> String {
+        let wkt: Wkt<f64> = Wkt::from_str(wkt).unwrap();
+        wkt.items[0].to_geojson()
+    }
+
+    #[test]
+    fn point_to_geojson() {
+        assert_eq!(
+            r#"{"type":"Point","coordinates":[1.5,-2]}"#,
+            geojson("POINT (1.5 -2)")
+        );
+        assert_eq!(
+            r#"{"type":"Point","coordinates":[1,2,3]}"#,
+            geojson("POINT ZM (1 2 3 4)")
+        );
+        assert_eq!(
+            r#"{"type":"Point","coordinates":[]}"#,
+            geojson("POINT EMPTY")
+        );
+    }
+
+    #[test]
+    fn non_finite_to_geojson() {
+        let wkt: Wkt<f64> = WktParser::new()
+            .non_finite(true)
+            .parse("POINT Z (NaN 2 inf)")
+            .unwrap();
+        assert_eq!(
+            r#"{"type":"Point","coordinates":[null,2,null]}"#,
+            wkt.items[0].to_geojson()
+        );
+    }
+
+    #[test]
+    fn polygon_with_hole_to_geojson() {
+        assert_eq!(
+            r#"{"type":"Polygon","coordinates":[[[35,10],[45,45],[15,40],[10,20],[35,10]],[[20,30],[35,35],[30,20],[20,30]]]}"#,
+            geojson("POLYGON ((35 10, 45 45, 15 40, 10 20, 35 10), (20 30, 35 35, 30 20, 20 30))")
+        );
+    }
+
+    #[cfg(feature = "geojson")]
+    fn from_geojson(json: &str) -> Result<Geometry<f64>, WktError> {
+        Geometry::from_geojson(&serde_json::from_str(json).unwrap())
+    }
+
+    #[test]
+    #[cfg(feature = "geojson")]
+    fn polygon_from_geojson() {
+        let wkt: Wkt<f64> = Wkt::from_str(
+            "POLYGON Z ((35 10 1, 45 45 1, 15 40 1, 35 10 1), (20 30 2, 35 35 2, 30 20 2, 20 30 2))",
+        )
+        .unwrap();
+        let geometry = from_geojson(
+            r#"{"type":"Polygon","coordinates":[[[35,10,1],[45,45,1],[15,40,1],[35,10,1]],[[20,30,2],[35,35,2],[30,20,2],[20,30,2]]]}"#,
+        )
+        .unwrap();
+        assert_eq!(format!("{:?}", wkt.items[0]), format!("{:?}", geometry));
+    }
+
+    #[test]
+    #[cfg(feature = "geojson")]
+    fn multipoint_from_geojson() {
+        let wkt: Wkt<f64> = Wkt::from_str("MULTIPOINT ((10 40), (40 30.5))").unwrap();
+        let geometry =
+            from_geojson(r#"{"type":"MultiPoint","coordinates":[[10,40],[40,30.5]]}"#).unwrap();
+        assert_eq!(format!("{:?}", wkt.items[0]), format!("{:?}", geometry));
+        assert_eq!(wkt.items[0].to_geojson(), geometry.to_geojson());
+    }
+
+    #[test]
+    #[cfg(feature = "geojson")]
+    fn invalid_geojson() {
+        assert_eq!(
+            WktError::InvalidGeoJson("expected a geometry, not a feature"),
+            from_geojson(r#"{"type":"Feature","geometry":null,"properties":{}}"#).unwrap_err()
+        );
+        assert_eq!(
+            WktError::InconsistentDimension {
+                expected: Dimension::XY,
+                found: Dimension::XYZ,
+            },
+            from_geojson(r#"{"type":"LineString","coordinates":[[1,2],[3,4,5]]}"#).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn collection_to_geojson() {
+        assert_eq!(
+            r#"{"type":"GeometryCollection","geometries":[{"type":"MultiPoint","coordinates":[[1,2],[3,4]]},{"type":"MultiLineString","coordinates":[[[0,0],[1,1]]]}]}"#,
+            geojson("GEOMETRYCOLLECTION (MULTIPOINT ((1 2), (3 4)), MULTILINESTRING ((0 0, 1 1)))")
+        );
+    }
+}
@@ -0,0 +1,220 @@
+// Copyright 2014-2021 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use core::hash::{Hash, Hasher};
+use types::{Coord, LineString, Point};
+use WktFloat;
+
+/// Wraps a [`Coord`], [`Point`] or [`LineString`] to implement `Hash` and `Eq`, which raw
+/// floats prevent, for example to deduplicate geometries in a `HashSet`.
+///
+/// Ordinates compare with `==`, except that every NaN equals every other NaN, and hash
+/// consistently with that: `0.0` and `-0.0` are equal and hash alike, as do all NaNs
+/// whatever their sign and payload. Dimension tags take part in both.
+///
+/// ```
+/// use std::collections::HashSet;
+/// use std::str::FromStr;
+/// use wkt::{Geometry, HashKey, Wkt};
+///
+/// let mut points = HashSet::new();
+/// for wkt in &["POINT (1 2)", "POINT (1.0 2.0)", "POINT (3 4)"] {
+///     if let Some(Geometry::Point(point)) = Wkt::<f64>::from_str(wkt).unwrap().items.pop() {
+///         points.insert(HashKey(point));
+///     }
+/// }
+/// assert_eq!(2, points.len());
+/// ```
+#[derive(Clone, Debug)]
+pub struct HashKey<G>(pub G);
+
+fn ordinate_eq<T: WktFloat>(a: T, b: T) -> bool {
+    a == b || (a.is_nan() && b.is_nan())
+}
+
+fn hash_ordinate<T: WktFloat, H: Hasher>(value: T, state: &mut H) {
+    if value.is_nan() {
+        state.write_u8(1);
+    } else if value.is_zero() {
+        state.write_u8(0);
+    } else {
+        value.integer_decode().hash(state);
+    }
+}
+
+fn coord_eq<T: WktFloat>(a: &Coord<T>, b: &Coord<T>) -> bool {
+    let optional = |a: Option<T>, b: Option<T>| match (a, b) {
+        (Some(a), Some(b)) => ordinate_eq(a, b),
+        (None, None) => true,
+        _ => false,
+    };
+    ordinate_eq(a.x, b.x) && ordinate_eq(a.y, b.y) && optional(a.z, b.z) && optional(a.m, b.m)
+}
+
+fn hash_coord<T: WktFloat, H: Hasher>(coord: &Coord<T>, state: &mut H) {
+    hash_ordinate(coord.x, state);
+    hash_ordinate(coord.y, state);
+    for ordinate in &[coord.z, coord.m] {
+        match *ordinate {
+            Some(value) => {
+                state.write_u8(1);
+                hash_ordinate(value, state);
+            }
+            None => state.write_u8(0),
+        }
+    }
+}
+
+impl<T: WktFloat> PartialEq for HashKey<Coord<T>> {
+    fn eq(&self, other: &Self) -> bool {
+        coord_eq(&self.0, &other.0)
+    }
+}
+
+impl<T: WktFloat> Eq for HashKey<Coord<T>> {}
+
+impl<T: WktFloat> Hash for HashKey<Coord<T>> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_coord(&self.0, state);
+    }
+}
+
+impl<T: WktFloat> PartialEq for HashKey<Point<T>> {
+    fn eq(&self, other: &Self) -> bool {
+        let coords = match (&self.0 .0, &other.0 .0) {
+            (Some(a), Some(b)) => coord_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        };
+        coords && self.0 .1 == other.0 .1
+    }
+}
+
+impl<T: WktFloat> Eq for HashKey<Point<T>> {}
+
+impl<T: WktFloat> Hash for HashKey<Point<T>> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self.0 .0 {
+            Some(ref coord) => {
+                state.write_u8(1);
+                hash_coord(coord, state);
+            }
+            None => state.write_u8(0),
+        }
+        self.0 .1.hash(state);
+    }
+}
+
+impl<T: WktFloat> PartialEq for HashKey<LineString<T>> {
+    fn eq(&self, other: &Self) -> bool {
+        let (a, b) = (&self.0 .0, &other.0 .0);
+        a.len() == b.len()
+            && a.iter().zip(b).all(|(a, b)| coord_eq(a, b))
+            && self.0 .1 == other.0 .1
+    }
+}
+
+impl<T: WktFloat> Eq for HashKey<LineString<T>> {}
+
+impl<T: WktFloat> Hash for HashKey<LineString<T>> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.0 .0.len());
+        self.0 .0.iter().for_each(|coord| hash_coord(coord, state));
+        self.0 .1.hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HashKey;
+    use std::collections::HashSet;
+    use types::{Coord, Dimension, LineString, Point};
+    use {Geometry, Wkt};
+
+    fn point(x: f64, y: f64) -> Point<f64> {
+        Point(
+            Some(Coord {
+                x,
+                y,
+                z: None,
+                m: None,
+            }),
+            Dimension::XY,
+        )
+    }
+
+    #[test]
+    fn equal_points_hash_once() {
+        let mut points = HashSet::new();
+        assert!(points.insert(HashKey(point(1., 2.))));
+        assert!(!points.insert(HashKey(point(1., 2.))));
+        assert_eq!(1, points.len());
+
+        // NaNs equal each other, and zeros of either sign too.
+        assert!(!points.insert(HashKey(point(1., 2.))));
+        assert!(points.insert(HashKey(point(f64::NAN, 0.))));
+        assert!(!points.insert(HashKey(point(-f64::NAN, -0.))));
+        assert!(points.insert(HashKey(Point(None, Dimension::XY))));
+        assert!(points.insert(HashKey(Point(None, Dimension::XYZ))));
+        assert_eq!(4, points.len());
+    }
+
+    #[test]
+    fn dedup_linestrings() {
+        let mut linestrings = HashSet::new();
+        for wkt in &[
+            "LINESTRING (1 2, 3 4)",
+            "LINESTRING (1 2, 3 4.0)",
+            "LINESTRING Z (1 2 0, 3 4 0)",
+            "LINESTRING (3 4, 1 2)",
+        ] {
+            match Wkt::<f64>::from_str(wkt).unwrap().items.pop() {
+                Some(Geometry::LineString(linestring)) => {
+                    linestrings.insert(HashKey(linestring));
+                }
+                _ => unreachable!(),
+            }
+        }
+        assert_eq!(3, linestrings.len());
+        assert!(linestrings.contains(&HashKey(LineString(
+            vec![
+                Coord {
+                    x: 3.,
+                    y: 4.,
+                    z: None,
+                    m: None
+                },
+                Coord {
+                    x: 1.,
+                    y: 2.,
+                    z: None,
+                    m: None
+                },
+            ],
+            Dimension::XY
+        ))));
+
+        let coords: HashSet<_> = [(0., 0.), (-0., 0.), (0., 1.)]
+            .iter()
+            .map(|&(x, y)| {
+                HashKey(Coord {
+                    x,
+                    y,
+                    z: None,
+                    m: None,
+                })
+            })
+            .collect();
+        assert_eq!(2, coords.len());
+    }
+}
@@ -0,0 +1,196 @@
+// Copyright 2014-2021 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use alloc::vec::Vec;
+use core::str::FromStr;
+use types::Dimension;
+use {Geometry, Wkt, WktError, WktFloat};
+
+/// A single geometry along with its metadata, see [`Wkt::parse_with_metadata`].
+#[derive(Clone, Debug)]
+pub struct ParsedWkt<T>
+where
+    T: WktFloat,
+{
+    /// The spatial reference id of an EWKT `SRID=4326;` prefix
+    pub srid: Option<u32>,
+    /// The dimension of the coordinates, see [`Geometry::dimension`]
+    pub dimension: Dimension,
+    pub item: Geometry<T>,
+}
+
+impl<T> Wkt<T>
+where
+    T: WktFloat + FromStr + Default,
+{
+    /// Parses a single geometry, optionally preceded by an EWKT `SRID=<id>;` prefix, along
+    /// with its SRID and dimension. The geometry type is available from
+    /// [`Geometry::geometry_type`]. Input without a geometry fails with
+    /// [`WktError::NoItems`], and coordinates of differing dimensions with
+    /// [`WktError::InconsistentDimension`].
+    pub fn parse_with_metadata(wkt_str: &str) -> Result<ParsedWkt<T>, WktError> {
+        let (srid, input) = split_srid(wkt_str)?;
+        let item = Wkt::from_str(input)?.items.pop().ok_or(WktError::NoItems)?;
+        Ok(ParsedWkt {
+            srid,
+            dimension: item.dimension()?,
+            item,
+        })
+    }
+}
+
+/// Splits a leading, case insensitive `SRID=<id>;` off `input`.
+pub(crate) fn split_srid(input: &str) -> Result<(Option<u32>, &str), WktError> {
+    let trimmed = input.trim_start();
+    match trimmed.get(..5) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("SRID=") => {
+            let (srid, rest) = trimmed[5..]
+                .split_once(';')
+                .ok_or(WktError::Syntax("Missing semicolon after SRID"))?;
+            let srid = srid
+                .trim()
+                .parse()
+                .map_err(|_| WktError::Syntax("Invalid SRID"))?;
+            Ok((Some(srid), rest))
+        }
+        _ => Ok((None, input)),
+    }
+}
+
+/// Whether `input` is an SRID prefix lacking its semicolon, as in `SRID=4326`.
+fn is_srid_prefix(input: &str) -> bool {
+    let trimmed = input.trim_start();
+    match trimmed.get(..5) {
+        Some(prefix) => prefix.eq_ignore_ascii_case("SRID=") && !trimmed.contains(';'),
+        None => false,
+    }
+}
+
+impl<T> Wkt<T>
+where
+    T: WktFloat + FromStr + Default,
+{
+    /// Parses several geometries separated by `;`, as in `POINT (1 2);LINESTRING (3 4, 5 6)`.
+    /// Each geometry may have an EWKT `SRID=<id>;` prefix, whose semicolon does not separate
+    /// geometries, and which is dropped. Blank segments, such as after a trailing `;`, are
+    /// skipped, while an SRID prefix without a geometry fails.
+    pub fn from_multi_str(wkt_str: &str) -> Result<Vec<Geometry<T>>, WktError> {
+        let mut geometries = Vec::new();
+        let mut start = 0;
+        let mut depth = 0usize;
+        for (i, c) in wkt_str.char_indices() {
+            match c {
+                '(' => depth += 1,
+                ')' => depth = depth.saturating_sub(1),
+                ';' if depth == 0 && !is_srid_prefix(&wkt_str[start..i]) => {
+                    geometries.extend(parse_segment(&wkt_str[start..i])?);
+                    start = i + 1;
+                }
+                _ => (),
+            }
+        }
+        geometries.extend(parse_segment(&wkt_str[start..])?);
+        Ok(geometries)
+    }
+}
+
+/// Parses one segment of [`Wkt::from_multi_str`], which may be blank but not an SRID
+/// prefix alone.
+fn parse_segment<T>(segment: &str) -> Result<Vec<Geometry<T>>, WktError>
+where
+    T: WktFloat + FromStr + Default,
+{
+    if segment.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    match split_srid(segment)? {
+        (Some(_), geometry) if geometry.trim().is_empty() => {
+            Err(WktError::Syntax("Missing geometry after SRID"))
+        }
+        (_, geometry) => Ok(Wkt::from_str(geometry)?.items),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use types::{Dimension, GeometryType};
+    use {Wkt, WktError};
+
+    #[test]
+    fn semicolon_separated() {
+        let geometries = Wkt::<f64>::from_multi_str("POINT (1 2);LINESTRING (3 4, 5 6)").unwrap();
+        assert_eq!(2, geometries.len());
+        assert_eq!("LINESTRING(3 4,5 6)", geometries[1].to_string());
+
+        let geometries = Wkt::<f64>::from_multi_str(
+            "POINT (1 2); POINT EMPTY ;\nPOLYGON ((0 0, 1 0, 1 1, 0 0));",
+        )
+        .unwrap();
+        assert_eq!(3, geometries.len());
+
+        let geometries =
+            Wkt::<f64>::from_multi_str("SRID=4326;POINT (1 2);srid=3857 ;POINT (3 4);POINT (5 6)")
+                .unwrap();
+        assert_eq!(
+            vec!["POINT(1 2)", "POINT(3 4)", "POINT(5 6)"],
+            geometries
+                .iter()
+                .map(|geometry| geometry.to_string())
+                .collect::<Vec<_>>()
+        );
+
+        assert_eq!(
+            WktError::Syntax("Invalid SRID"),
+            Wkt::<f64>::from_multi_str("POINT (1 2);SRID=x;POINT (3 4)").unwrap_err()
+        );
+        assert_eq!(
+            WktError::Syntax("Missing semicolon after SRID"),
+            Wkt::<f64>::from_multi_str("POINT (1 2);SRID=4326").unwrap_err()
+        );
+        assert_eq!(
+            WktError::Syntax("Missing geometry after SRID"),
+            Wkt::<f64>::from_multi_str("POINT (1 2);SRID=4326;").unwrap_err()
+        );
+        assert_eq!(
+            WktError::Syntax("Missing semicolon after SRID"),
+            Wkt::<f64>::from_multi_str("SRID=4326").unwrap_err()
+        );
+    }
+
+    #[test]
+    fn plain_metadata() {
+        let parsed = Wkt::<f64>::parse_with_metadata("LINESTRING (1 2, 3 4)").unwrap();
+        assert_eq!(None, parsed.srid);
+        assert_eq!(Dimension::XY, parsed.dimension);
+        assert_eq!("LineString", parsed.item.geometry_type().name());
+    }
+
+    #[test]
+    fn ewkt_metadata() {
+        let parsed = Wkt::<f64>::parse_with_metadata("SRID=4326;POINT Z (1 2 3)").unwrap();
+        assert_eq!(Some(4326), parsed.srid);
+        assert_eq!(Dimension::XYZ, parsed.dimension);
+        assert_eq!(GeometryType::Point, parsed.item.geometry_type());
+        assert_eq!("POINT Z(1 2 3)", parsed.item.to_string());
+
+        assert_eq!(
+            WktError::Syntax("Invalid SRID"),
+            Wkt::<f64>::parse_with_metadata("SRID=abc;POINT (1 2)").unwrap_err()
+        );
+        assert_eq!(
+            WktError::NoItems,
+            Wkt::<f64>::parse_with_metadata("srid=4326;").unwrap_err()
+        );
+        Wkt::<f64>::from_str("SRID=4326;POINT (1 2)").unwrap_err();
+    }
+}
@@ -0,0 +1,680 @@
+// Copyright 2014-2021 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::str::FromStr;
+#[cfg(feature = "std")]
+use std::io::{BufRead, Read};
+use tokenizer::Tokens;
+use {Geometry, Wkt, WktError, WktNum};
+
+/// The default of [`WktParser::max_depth`], which also bounds nesting in the binary
+/// formats.
+pub(crate) const DEFAULT_MAX_DEPTH: usize = 128;
+
+/// A configurable WKT parser.
+///
+/// The default configuration is strict and behaves like [`Wkt::from_str`]. Each option
+/// accepts one kind of non-standard input; [`WktParser::lenient`] enables all of them.
+///
+/// ```
+/// use wkt::WktParser;
+///
+/// let wkt = WktParser::new()
+///     .comments(true)
+///     .parse::<f64>("# city hall\nPOINT (1 2)")
+///     .unwrap();
+/// assert_eq!(Some("city hall"), wkt.comments[0].as_deref());
+/// ```
+#[derive(Clone, Debug)]
+pub struct WktParser {
+    comments: bool,
+    feature_ids: bool,
+    pub(crate) multipoint_without_commas: bool,
+    pub(crate) max_depth: usize,
+    pub(crate) trailing_semicolon: bool,
+    pub(crate) type_qualifier: bool,
+    quoted_numbers: bool,
+    coordinate_counts: bool,
+    pub(crate) non_finite: bool,
+    pub(crate) empty_parens_mean_empty: bool,
+    pub(crate) commas_in_coordinates: bool,
+    pub(crate) spans: bool,
+    max_line_bytes: usize,
+}
+
+impl Default for WktParser {
+    fn default() -> Self {
+        WktParser {
+            comments: false,
+            feature_ids: false,
+            multipoint_without_commas: false,
+            max_depth: DEFAULT_MAX_DEPTH,
+            trailing_semicolon: false,
+            type_qualifier: false,
+            quoted_numbers: false,
+            coordinate_counts: false,
+            non_finite: false,
+            empty_parens_mean_empty: false,
+            commas_in_coordinates: false,
+            spans: false,
+            max_line_bytes: usize::MAX,
+        }
+    }
+}
+
+impl WktParser {
+    /// A strict parser
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A parser accepting every supported non-standard input
+    pub fn lenient() -> Self {
+        WktParser {
+            comments: true,
+            feature_ids: true,
+            multipoint_without_commas: true,
+            trailing_semicolon: true,
+            type_qualifier: true,
+            quoted_numbers: true,
+            non_finite: true,
+            empty_parens_mean_empty: true,
+            commas_in_coordinates: true,
+            ..Self::default()
+        }
+    }
+
+    /// Captures `#` comment lines preceding the geometry into [`Wkt::comments`]. When
+    /// disabled, a comment is a syntax error.
+    pub fn comments(mut self, enabled: bool) -> Self {
+        self.comments = enabled;
+        self
+    }
+
+    /// Records the byte range of each item in the input into [`Wkt::spans`], for example to
+    /// highlight it in an editor. The range excludes comments, feature ids and a trailing
+    /// semicolon.
+    pub fn spans(mut self, enabled: bool) -> Self {
+        self.spans = enabled;
+        self
+    }
+
+    /// Captures a numeric feature id prefixing the geometry, as in `42: POINT (1 2)` from
+    /// log output, into [`Wkt::ids`]. When disabled, the prefix is a syntax error.
+    pub fn feature_ids(mut self, enabled: bool) -> Self {
+        self.feature_ids = enabled;
+        self
+    }
+
+    /// Accepts two dimensional `MULTIPOINT`s whose bare points are not separated by commas,
+    /// reading `MULTIPOINT (1 1 2 2)` as two points. The input is ambiguous, so this only
+    /// applies when no dimension tag is given.
+    pub fn multipoint_without_commas(mut self, enabled: bool) -> Self {
+        self.multipoint_without_commas = enabled;
+        self
+    }
+
+    /// Limits how deeply `GEOMETRYCOLLECTION`s may be nested, 128 by default. Deeper input
+    /// fails with [`WktError::MaxDepthExceeded`] rather than exhausting the stack.
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = depth;
+        self
+    }
+
+    /// Accepts a single `;` terminating the geometry, as in SQL output. Anything following
+    /// the `;` fails, see [`Wkt::from_multi_str`] for several geometries.
+    pub fn trailing_semicolon(mut self, enabled: bool) -> Self {
+        self.trailing_semicolon = enabled;
+        self
+    }
+
+    /// Accepts the geometry wrapped in a SQL `GEOMETRY(...)` or `GEOGRAPHY(...)` type
+    /// qualifier, which is stripped.
+    pub fn type_qualifier(mut self, enabled: bool) -> Self {
+        self.type_qualifier = enabled;
+        self
+    }
+
+    /// Accepts ordinates wrapped in double quotes, as in `POINT ("1" "2")` from CSV exports.
+    pub fn quoted_numbers(mut self, enabled: bool) -> Self {
+        self.quoted_numbers = enabled;
+        self
+    }
+
+    /// Rejects geometries with fewer coordinates than the specification allows, such as a
+    /// single coordinate `LINESTRING`, see [`Geometry::validate_coordinate_counts`].
+    pub fn coordinate_counts(mut self, enabled: bool) -> Self {
+        self.coordinate_counts = enabled;
+        self
+    }
+
+    /// Accepts `NaN`, `inf` and `-inf` ordinates, which are otherwise rejected with
+    /// [`WktError::InvalidNumber`]. Geometries are written with the same spellings, so
+    /// they round-trip.
+    pub fn non_finite(mut self, enabled: bool) -> Self {
+        self.non_finite = enabled;
+        self
+    }
+
+    /// Reads empty parentheses, as in `POINT ()`, like the `EMPTY` keyword rather than as
+    /// a syntax error.
+    pub fn empty_parens_mean_empty(mut self, enabled: bool) -> Self {
+        self.empty_parens_mean_empty = enabled;
+        self
+    }
+
+    /// Accepts commas between the ordinates of a coordinate, as in `POINT (10, 20)` from
+    /// legacy exporters, as long as the coordinate lacks ordinates for its dimension.
+    pub fn commas_in_coordinates(mut self, enabled: bool) -> Self {
+        self.commas_in_coordinates = enabled;
+        self
+    }
+
+    /// Limits the length of the lines read by [`WktParser::parse_lines`], excluding the line
+    /// terminator, so that untrusted input without newlines cannot exhaust memory.
+    pub fn max_line_bytes(mut self, bytes: usize) -> Self {
+        self.max_line_bytes = bytes;
+        self
+    }
+
+    /// Parses one geometry per line of `reader`, skipping blank lines. Lines may end with
+    /// `\n`, `\r\n` or `\r`, mixed in one input, and the last needs no ending. A line longer
+    /// than [`WktParser::max_line_bytes`] fails with [`WktError::LineTooLong`] before it is
+    /// read in full.
+    #[cfg(feature = "std")]
+    pub fn parse_lines<T, R>(&self, mut reader: R) -> Result<Wkt<T>, WktError>
+    where
+        T: WktNum + FromStr + Default,
+        R: BufRead,
+    {
+        let mut wkt = Wkt::new();
+        let mut buf = Vec::new();
+        let mut position = 0;
+        for line in 1.. {
+            buf.clear();
+            let limit = self.max_line_bytes.saturating_add(2) as u64;
+            let read = (&mut reader)
+                .take(limit)
+                .read_until(b'\n', &mut buf)
+                .map_err(|e| WktError::Io(e.to_string()))?;
+            if read == 0 {
+                break;
+            }
+            let line_start = position;
+            position += read;
+            let text = buf.strip_suffix(b"\n").unwrap_or(&buf);
+            let text = text.strip_suffix(b"\r").unwrap_or(text);
+            if text.len() > self.max_line_bytes {
+                return Err(WktError::LineTooLong {
+                    line,
+                    max: self.max_line_bytes,
+                });
+            }
+            let text = core::str::from_utf8(text)
+                .map_err(|_| WktError::Syntax("Line is not valid UTF-8"))?;
+            // A lone `\r`, left by mixed or classic Mac OS line endings, also ends a line,
+            // rather than hiding the geometries following it on the same line.
+            let mut part_start = line_start;
+            for part in text.split('\r') {
+                let offset = part_start;
+                part_start += part.len() + 1;
+                if part.trim().is_empty() {
+                    continue;
+                }
+                let parsed = self.parse(part)?;
+                wkt.items.extend(parsed.items);
+                wkt.comments.extend(parsed.comments);
+                wkt.ids.extend(parsed.ids);
+                let shift = |(start, end)| (offset + start, offset + end);
+                wkt.spans
+                    .extend(parsed.spans.into_iter().map(|span| span.map(shift)));
+            }
+        }
+        Ok(wkt)
+    }
+
+    /// Parses `wkt_str`, skipping a leading byte order mark.
+    pub fn parse<T>(&self, wkt_str: &str) -> Result<Wkt<T>, WktError>
+    where
+        T: WktNum + FromStr + Default,
+    {
+        let input = wkt_str.strip_prefix('\u{FEFF}').unwrap_or(wkt_str);
+        let (comment, input) = if self.comments {
+            split_comment(input)
+        } else {
+            (None, input)
+        };
+
+        let (id, input) = if self.feature_ids {
+            split_feature_id(input)?
+        } else {
+            (None, input)
+        };
+
+        if let Some((i, character)) = input.char_indices().find(|(_, c)| !c.is_ascii()) {
+            return Err(WktError::NonAscii {
+                character,
+                position: wkt_str.len() - input.len() + i,
+            });
+        }
+
+        let mut wkt = Wkt::from_tokens(
+            Tokens::from_str(input).quoted_numbers(self.quoted_numbers),
+            self,
+        )?;
+        if self.coordinate_counts {
+            wkt.items
+                .iter()
+                .try_for_each(Geometry::validate_coordinate_counts)?;
+        }
+        if let Some(slot) = wkt.comments.first_mut() {
+            *slot = comment;
+        }
+        if let Some(slot) = wkt.ids.first_mut() {
+            *slot = id;
+        }
+        // The tokens only cover the input following the prefixes.
+        let offset = wkt_str.len() - input.len();
+        for (start, end) in wkt.spans.iter_mut().flatten() {
+            *start += offset;
+            *end += offset;
+        }
+        Ok(wkt)
+    }
+}
+
+/// Splits the leading `#` comment lines off `input`, joining them with newlines.
+fn split_comment(mut input: &str) -> (Option<String>, &str) {
+    let mut lines: Vec<&str> = Vec::new();
+    loop {
+        let trimmed = input.trim_start();
+        if !trimmed.starts_with('#') {
+            break;
+        }
+        let end = trimmed.find('\n').unwrap_or(trimmed.len());
+        lines.push(trimmed[1..end].trim());
+        input = &trimmed[end..];
+    }
+
+    if lines.is_empty() {
+        (None, input)
+    } else {
+        (Some(lines.join("\n")), input)
+    }
+}
+
+/// Splits a leading `<digits>:` feature id off `input`.
+fn split_feature_id(input: &str) -> Result<(Option<u64>, &str), WktError> {
+    let trimmed = input.trim_start();
+    let end = trimmed
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(trimmed.len());
+    match trimmed[end..].trim_start().strip_prefix(':') {
+        Some(rest) if end > 0 => {
+            let id = trimmed[..end]
+                .parse()
+                .map_err(|_| WktError::Syntax("Feature id out of range"))?;
+            Ok((Some(id), rest))
+        }
+        _ => Ok((None, input)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WktParser;
+    use types::Dimension;
+    use types::{Coord, Point};
+    use {Geometry, Wkt, WktError};
+
+    #[test]
+    fn parse_comment() {
+        let wkt: Wkt<f64> = WktParser::new()
+            .comments(true)
+            .parse("  # first line\r\n#second line\nPOINT (1 2)")
+            .unwrap();
+        assert_eq!(
+            vec![Some("first line\nsecond line".to_string())],
+            wkt.comments
+        );
+        assert!(matches!(
+            wkt.items[0],
+            Geometry::Point(Point(Some(Coord { z: None, .. }), Dimension::XY))
+        ));
+    }
+
+    #[test]
+    fn parse_without_comment() {
+        let wkt: Wkt<f64> = WktParser::lenient().parse("POINT (1 2)").unwrap();
+        assert_eq!(vec![None], wkt.comments);
+    }
+
+    #[test]
+    fn spans() {
+        let input = "# first\n 42: POINT (1 2) ;";
+        let wkt: Wkt<f64> = WktParser::lenient().spans(true).parse(input).unwrap();
+        assert_eq!(vec![Some((13, 24))], wkt.spans);
+        assert_eq!("POINT (1 2)", &input[13..24]);
+
+        #[cfg(feature = "std")]
+        {
+            let input = "POINT EMPTY \n\n  LINESTRING (1 2, 3 4)\r\n";
+            let wkt: Wkt<f64> = WktParser::new()
+                .spans(true)
+                .parse_lines(input.as_bytes())
+                .unwrap();
+            let spans: Vec<&str> = wkt
+                .spans
+                .iter()
+                .map(|span| {
+                    let (start, end) = span.unwrap();
+                    &input[start..end]
+                })
+                .collect();
+            assert_eq!(vec!["POINT EMPTY", "LINESTRING (1 2, 3 4)"], spans);
+        }
+
+        let wkt: Wkt<f64> = Wkt::from_str("POINT (1 2)").unwrap();
+        assert_eq!(vec![None], wkt.spans);
+    }
+
+    #[test]
+    fn feature_id() {
+        let wkt: Wkt<f64> = WktParser::new()
+            .feature_ids(true)
+            .parse("42: POINT (1 2)")
+            .unwrap();
+        assert_eq!(vec![Some(42)], wkt.ids);
+        assert_eq!("POINT(1 2)", wkt.items[0].to_string());
+
+        let wkt: Wkt<f64> = WktParser::lenient().parse("POINT (1 2)").unwrap();
+        assert_eq!(vec![None], wkt.ids);
+        WktParser::lenient()
+            .parse::<f64>("99999999999999999999: POINT (1 2)")
+            .err()
+            .unwrap();
+        <Wkt<f64>>::from_str("42: POINT (1 2)").err().unwrap();
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn parse_lines() {
+        let input = "POINT (1 2)\r\n\nLINESTRING (1 2, 3 4)\nPOINT (5 6)";
+        let wkt: Wkt<f64> = WktParser::new()
+            .max_line_bytes(21)
+            .parse_lines(input.as_bytes())
+            .unwrap();
+        assert_eq!(3, wkt.items.len());
+        assert_eq!(3, wkt.comments.len());
+        assert_eq!("POINT(5 6)", wkt.items[2].to_string());
+
+        assert_eq!(
+            WktError::LineTooLong { line: 3, max: 20 },
+            WktParser::new()
+                .max_line_bytes(20)
+                .parse_lines::<f64, _>(input.as_bytes())
+                .err()
+                .unwrap()
+        );
+        let endless = "1 ".repeat(100_000);
+        assert_eq!(
+            WktError::LineTooLong { line: 1, max: 64 },
+            WktParser::new()
+                .max_line_bytes(64)
+                .parse_lines::<f64, _>(endless.as_bytes())
+                .err()
+                .unwrap()
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn parse_lines_endings() {
+        let lf = "POINT (1 2)\n\nLINESTRING (1 2, 3 4)\n  \nPOLYGON ((0 0, 1 0, 1 1, 0 0))\n";
+        let crlf = lf.replace('\n', "\r\n");
+        let mixed = "POINT (1 2)\r\rLINESTRING (1 2, 3 4)\n  \r\nPOLYGON ((0 0, 1 0, 1 1, 0 0))";
+        let parse = |input: &str| {
+            WktParser::new()
+                .spans(true)
+                .parse_lines::<f64, _>(input.as_bytes())
+                .unwrap()
+        };
+        let expected = parse(lf);
+        assert_eq!(3, expected.items.len());
+        for input in &[crlf.as_str(), mixed, lf.trim_end()] {
+            let wkt = parse(input);
+            assert_eq!(format!("{:?}", expected.items), format!("{:?}", wkt.items));
+            for span in wkt.spans {
+                let (start, end) = span.unwrap();
+                assert!(!input[start..end].contains('\r'));
+            }
+        }
+
+        assert_eq!(
+            2,
+            Wkt::<f64>::from_multi_str("POINT (1 2);\r\nPOINT (3 4);\r\n")
+                .unwrap()
+                .len()
+        );
+    }
+
+    #[test]
+    fn multipoint_without_commas() {
+        let wkt: Wkt<f64> = WktParser::new()
+            .multipoint_without_commas(true)
+            .parse("MULTIPOINT (1 1 2 2)")
+            .unwrap();
+        assert_eq!("MULTIPOINT((1 1),(2 2))", wkt.items[0].to_string());
+
+        WktParser::new()
+            .parse::<f64>("MULTIPOINT (1 1 2 2)")
+            .err()
+            .unwrap();
+        WktParser::lenient()
+            .parse::<f64>("MULTIPOINT Z (1 1 2 2)")
+            .err()
+            .unwrap();
+    }
+
+    #[test]
+    fn nesting_limit() {
+        let nested =
+            |depth| "GEOMETRYCOLLECTION (".repeat(depth) + "POINT (1 2)" + &")".repeat(depth);
+        let parser = WktParser::new().max_depth(3);
+        parser.parse::<f64>(&nested(3)).unwrap();
+        assert_eq!(
+            WktError::MaxDepthExceeded,
+            parser.parse::<f64>(&nested(4)).err().unwrap()
+        );
+
+        <Wkt<f64>>::from_str(&nested(128)).unwrap();
+        assert_eq!(
+            WktError::MaxDepthExceeded,
+            <Wkt<f64>>::from_str(&nested(100_000)).err().unwrap()
+        );
+    }
+
+    #[test]
+    fn trailing_semicolon() {
+        let wkt: Wkt<f64> = WktParser::lenient().parse("POINT (1 2);").unwrap();
+        assert_eq!("POINT(1 2)", wkt.items[0].to_string());
+        WktParser::lenient().parse::<f64>("POINT (1 2) ;").unwrap();
+        WktParser::lenient()
+            .parse::<f64>("POINT (1 2);;")
+            .err()
+            .unwrap();
+        assert_eq!(
+            WktError::Syntax("Unexpected content after trailing semicolon"),
+            WktParser::lenient()
+                .parse::<f64>("POINT (1 2); POINT (3 4)")
+                .err()
+                .unwrap()
+        );
+        <Wkt<f64>>::from_str("POINT (1 2);").err().unwrap();
+    }
+
+    #[test]
+    fn type_qualifier() {
+        let wkt: Wkt<f64> = WktParser::lenient().parse("GEOMETRY(POINT (1 2))").unwrap();
+        assert_eq!("POINT(1 2)", wkt.items[0].to_string());
+        let wkt: Wkt<f64> = WktParser::new()
+            .type_qualifier(true)
+            .parse("geography ( LINESTRING (1 2, 3 4) )")
+            .unwrap();
+        assert_eq!("LINESTRING(1 2,3 4)", wkt.items[0].to_string());
+        WktParser::new()
+            .type_qualifier(true)
+            .parse::<f64>("GEOMETRY(POINT (1 2)")
+            .err()
+            .unwrap();
+        <Wkt<f64>>::from_str("GEOMETRY(POINT (1 2))").err().unwrap();
+    }
+
+    #[test]
+    fn commas_in_coordinates() {
+        let wkt: Wkt<f64> = WktParser::lenient().parse("POINT (10, 20)").unwrap();
+        assert_eq!("POINT(10 20)", wkt.items[0].to_string());
+        let parser = WktParser::new().commas_in_coordinates(true);
+        let wkt: Wkt<f64> = parser.parse("LINESTRING Z (1, 2, 3, 4, 5, 6)").unwrap();
+        assert_eq!("LINESTRING Z(1 2 3,4 5 6)", wkt.items[0].to_string());
+        let wkt: Wkt<f64> = parser.parse("LINESTRING (1, 2, 3, 4)").unwrap();
+        assert_eq!("LINESTRING(1 2,3 4)", wkt.items[0].to_string());
+
+        assert_eq!(
+            WktError::Syntax("Expected a number for the Y coordinate"),
+            <Wkt<f64>>::from_str("POINT (10, 20)").err().unwrap()
+        );
+    }
+
+    #[test]
+    fn quoted_numbers() {
+        let wkt: Wkt<f64> = WktParser::lenient().parse(r#"POINT ("1" "2")"#).unwrap();
+        assert_eq!("POINT(1 2)", wkt.items[0].to_string());
+        let wkt: Wkt<f64> = WktParser::new()
+            .quoted_numbers(true)
+            .parse(r#"LINESTRING ("1" 2,3 "-4.5")"#)
+            .unwrap();
+        assert_eq!("LINESTRING(1 2,3 -4.5)", wkt.items[0].to_string());
+
+        WktParser::lenient()
+            .parse::<f64>(r#"POINT ("1 2")"#)
+            .err()
+            .unwrap();
+        <Wkt<f64>>::from_str(r#"POINT ("1" "2")"#).err().unwrap();
+    }
+
+    #[test]
+    fn coordinate_counts() {
+        WktParser::new().parse::<f64>("LINESTRING (10 20)").unwrap();
+        assert_eq!(
+            WktError::TooFewCoordinates {
+                geometry: "LineString",
+                min: 2,
+                found: 1,
+            },
+            WktParser::new()
+                .coordinate_counts(true)
+                .parse::<f64>("LINESTRING (10 20)")
+                .err()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn non_finite_round_trip() {
+        let parser = WktParser::new().non_finite(true);
+        let wkt: Wkt<f64> = parser.parse("POINT (NaN 2)").unwrap();
+        let written = wkt.items[0].to_string();
+        assert_eq!("POINT(NaN 2)", written);
+        match &parser.parse::<f64>(&written).unwrap().items[0] {
+            Geometry::Point(Point(Some(coord), _)) => {
+                assert!(coord.x.is_nan());
+                assert_eq!(2., coord.y);
+            }
+            _ => unreachable!(),
+        }
+
+        let wkt: Wkt<f64> = parser.parse("LINESTRING (inf 1, 2 -inf)").unwrap();
+        assert_eq!("LINESTRING(inf 1,2 -inf)", wkt.items[0].to_string());
+        WktParser::new()
+            .parse::<f64>("POINT (NaN 2)")
+            .err()
+            .unwrap();
+    }
+
+    #[test]
+    fn empty_parens() {
+        let parser = WktParser::new().empty_parens_mean_empty(true);
+        let wkt: Wkt<f64> = parser.parse("POINT ()").unwrap();
+        assert!(matches!(
+            wkt.items[0],
+            Geometry::Point(Point(None, Dimension::XY))
+        ));
+        let wkt: Wkt<f64> = parser
+            .parse("GEOMETRYCOLLECTION (LINESTRING Z (), POINT (1 2))")
+            .unwrap();
+        assert_eq!(
+            "GEOMETRYCOLLECTION(LINESTRING Z EMPTY,POINT(1 2))",
+            wkt.items[0].to_string()
+        );
+    }
+
+    #[test]
+    fn reject_empty_parens_by_default() {
+        assert_eq!(
+            WktError::Syntax("Expected a number for the X coordinate"),
+            WktParser::new().parse::<f64>("POINT ()").err().unwrap()
+        );
+        <Wkt<f64>>::from_str("LINESTRING ()").err().unwrap();
+    }
+
+    #[test]
+    fn byte_order_mark() {
+        let wkt: Wkt<f64> = Wkt::from_str("\u{FEFF}POINT (1 2)").unwrap();
+        assert_eq!("POINT(1 2)", wkt.items[0].to_string());
+    }
+
+    #[test]
+    fn non_ascii() {
+        assert_eq!(
+            WktError::NonAscii {
+                character: 'é',
+                position: 10,
+            },
+            <Wkt<f64>>::from_str("\u{FEFF}POINT (é 2)").err().unwrap()
+        );
+        assert_eq!(
+            WktError::NonAscii {
+                character: 'ı',
+                position: 10,
+            },
+            WktParser::new()
+                .comments(true)
+                .parse::<f64>("# café\nPOıNT (1 2)")
+                .err()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn reject_comment_by_default() {
+        WktParser::new()
+            .parse::<f64>("# label\nPOINT (1 2)")
+            .err()
+            .unwrap();
+        <Wkt<f64>>::from_str("# label\nPOINT (1 2)").err().unwrap();
+    }
+}
@@ -0,0 +1,412 @@
+// Copyright 2014-2021 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Checking the syntax of WKT without building geometries, see [`Wkt::validate_str`].
+//!
+//! Each geometry type has a zero-sized counterpart, parsed with the same [`FromTokens`]
+//! methods, so that the lists collected by [`FromTokens::comma_many`] never allocate. Only
+//! the coordinate lists whose length is checked keep a count.
+//!
+//! For a linestring of 100,000 `f64` coordinates, [`Wkt::from_str`] holds at least 4.8 MB of
+//! 48 byte `Coord`s, more while the `Vec` grows, whereas `validate_str` makes no heap
+//! allocation at all. Only the rings of the polygon being checked are counted on the heap.
+
+use alloc::vec::Vec;
+use core::str::FromStr;
+use tokenizer::{PeekableTokens, Token, Tokens};
+use types::{Coord, Dimension};
+use {
+    is_crs_keyword, parse, split_dimension_suffix, FromTokens, Wkt, WktError, WktFloat, WktParser,
+};
+
+/// A coordinate, parsed and dropped
+#[derive(Default)]
+struct SkippedCoord;
+
+impl<T> FromTokens<T> for SkippedCoord
+where
+    T: WktFloat + FromStr + Default,
+{
+    fn from_tokens(tokens: &mut PeekableTokens<T>, dim: Dimension) -> Result<Self, WktError> {
+        <Coord<T> as FromTokens<T>>::from_tokens(tokens, dim).map(|_| SkippedCoord)
+    }
+}
+
+/// The number of coordinates of a linestring or ring
+#[derive(Default)]
+struct SkippedCoords(usize);
+
+impl<T> FromTokens<T> for SkippedCoords
+where
+    T: WktFloat + FromStr + Default,
+{
+    fn from_tokens(tokens: &mut PeekableTokens<T>, dim: Dimension) -> Result<Self, WktError> {
+        FromTokens::comma_many(<SkippedCoord as FromTokens<T>>::from_tokens, tokens, dim)
+            .map(|coords: Vec<SkippedCoord>| SkippedCoords(coords.len()))
+    }
+}
+
+/// The rings of a polygon, or the linestrings of a multilinestring
+#[derive(Default)]
+struct SkippedRings;
+
+impl<T> FromTokens<T> for SkippedRings
+where
+    T: WktFloat + FromStr + Default,
+{
+    fn from_tokens(tokens: &mut PeekableTokens<T>, dim: Dimension) -> Result<Self, WktError> {
+        FromTokens::comma_many(
+            <SkippedCoords as FromTokens<T>>::from_tokens_with_parens,
+            tokens,
+            dim,
+        )
+        .map(|_| SkippedRings)
+    }
+}
+
+/// The polygons of a multipolygon or polyhedral surface
+#[derive(Default)]
+struct SkippedPolygons;
+
+impl<T> FromTokens<T> for SkippedPolygons
+where
+    T: WktFloat + FromStr + Default,
+{
+    fn from_tokens(tokens: &mut PeekableTokens<T>, dim: Dimension) -> Result<Self, WktError> {
+        FromTokens::comma_many(
+            <SkippedRings as FromTokens<T>>::from_tokens_with_parens,
+            tokens,
+            dim,
+        )
+        .map(|_| SkippedPolygons)
+    }
+}
+
+/// A triangle, checked like [`Triangle`](crate::types::Triangle)
+#[derive(Default)]
+struct SkippedTriangle;
+
+impl<T> FromTokens<T> for SkippedTriangle
+where
+    T: WktFloat + FromStr + Default,
+{
+    fn from_tokens(tokens: &mut PeekableTokens<T>, dim: Dimension) -> Result<Self, WktError> {
+        let rings: Vec<SkippedCoords> = FromTokens::comma_many(
+            <SkippedCoords as FromTokens<T>>::from_tokens_with_parens,
+            tokens,
+            dim,
+        )?;
+        if rings.len() != 1 {
+            return Err(WktError::Syntax("Expected exactly one ring in TRIANGLE"));
+        }
+        if rings[0].0 != 4 {
+            return Err(WktError::WrongNumberOfCoordinates {
+                geometry: "Triangle",
+                expected: 4,
+                found: rings[0].0,
+            });
+        }
+        Ok(SkippedTriangle)
+    }
+}
+
+/// The triangles of a TIN
+#[derive(Default)]
+struct SkippedTin;
+
+impl<T> FromTokens<T> for SkippedTin
+where
+    T: WktFloat + FromStr + Default,
+{
+    fn from_tokens(tokens: &mut PeekableTokens<T>, dim: Dimension) -> Result<Self, WktError> {
+        FromTokens::comma_many(
+            <SkippedTriangle as FromTokens<T>>::from_tokens_with_parens,
+            tokens,
+            dim,
+        )
+        .map(|_| SkippedTin)
+    }
+}
+
+/// A circular string, checked like [`CircularString`](crate::types::CircularString)
+#[derive(Default)]
+struct SkippedCircularString;
+
+impl<T> FromTokens<T> for SkippedCircularString
+where
+    T: WktFloat + FromStr + Default,
+{
+    fn from_tokens(tokens: &mut PeekableTokens<T>, dim: Dimension) -> Result<Self, WktError> {
+        let SkippedCoords(count) = FromTokens::from_tokens(tokens, dim)?;
+        if count < 3 {
+            return Err(WktError::TooFewCoordinates {
+                geometry: "CircularString",
+                min: 3,
+                found: count,
+            });
+        }
+        if count % 2 == 0 {
+            return Err(WktError::Syntax(
+                "Expected an odd number of coordinates in CIRCULARSTRING",
+            ));
+        }
+        Ok(SkippedCircularString)
+    }
+}
+
+/// The corners of a box, checked like [`Box2D`](crate::types::Box2D)
+#[derive(Default)]
+struct SkippedBox;
+
+impl<T> FromTokens<T> for SkippedBox
+where
+    T: WktFloat + FromStr + Default,
+{
+    fn from_tokens(tokens: &mut PeekableTokens<T>, dim: Dimension) -> Result<Self, WktError> {
+        if let Some(Token::ParenClose) = tokens.peek() {
+            return Err(WktError::WrongNumberOfCoordinates {
+                geometry: "Box",
+                expected: 2,
+                found: 0,
+            });
+        }
+        let SkippedCoords(count) = FromTokens::from_tokens(tokens, dim)?;
+        if count != 2 {
+            return Err(WktError::WrongNumberOfCoordinates {
+                geometry: "Box",
+                expected: 2,
+                found: count,
+            });
+        }
+        Ok(SkippedBox)
+    }
+}
+
+/// The points of a multipoint, which may be written bare
+#[derive(Default)]
+struct SkippedMultiPoint;
+
+impl<T> FromTokens<T> for SkippedMultiPoint
+where
+    T: WktFloat + FromStr + Default,
+{
+    fn from_tokens(tokens: &mut PeekableTokens<T>, dim: Dimension) -> Result<Self, WktError> {
+        let without_commas = dim == Dimension::XY && tokens.options().multipoint_without_commas;
+        let check_arity = tokens.set_check_arity(!without_commas);
+        let points = skip_points(tokens, dim, without_commas);
+        tokens.set_check_arity(check_arity);
+        points.map(|_| SkippedMultiPoint)
+    }
+}
+
+fn skip_points<T>(
+    tokens: &mut PeekableTokens<T>,
+    dim: Dimension,
+    without_commas: bool,
+) -> Result<(), WktError>
+where
+    T: WktFloat + FromStr + Default,
+{
+    loop {
+        match tokens.peek() {
+            Some(Token::Number(_)) => <SkippedCoord as FromTokens<T>>::from_tokens(tokens, dim)?,
+            _ => <SkippedCoord as FromTokens<T>>::from_tokens_with_parens(tokens, dim)?,
+        };
+        match tokens.peek() {
+            Some(Token::Comma) => {
+                tokens.next();
+            }
+            Some(Token::Number(_)) if without_commas => (),
+            _ => return Ok(()),
+        }
+    }
+}
+
+/// The members of a geometry collection
+#[derive(Default)]
+struct SkippedCollection;
+
+impl<T> FromTokens<T> for SkippedCollection
+where
+    T: WktFloat + FromStr + Default,
+{
+    fn from_tokens(tokens: &mut PeekableTokens<T>, _dim: Dimension) -> Result<Self, WktError> {
+        tokens.enter_collection()?;
+        loop {
+            let word = match tokens.next().transpose()? {
+                Some(Token::Word(w)) => w,
+                _ => return Err(WktError::Syntax("Expected a word in GEOMETRYCOLLECTION")),
+            };
+            skip_geometry(&word, tokens)?;
+            match tokens.peek() {
+                Some(&Token::Comma) => tokens.next(),
+                _ => break,
+            };
+        }
+        tokens.exit_collection();
+        Ok(SkippedCollection)
+    }
+}
+
+/// Checks the geometry introduced by `word` like `Geometry::from_word_and_tokens` parses it.
+fn skip_geometry<T>(word: &str, tokens: &mut PeekableTokens<T>) -> Result<(), WktError>
+where
+    T: WktFloat + FromStr + Default,
+{
+    let (keyword, dim) = split_dimension_suffix(word);
+    let is = |expected: &str| keyword.eq_ignore_ascii_case(expected);
+    if is("POINT") {
+        parse::<SkippedCoord, T>(tokens, dim).map(drop)
+    } else if is("LINESTRING") {
+        parse::<SkippedCoords, T>(tokens, dim).map(drop)
+    } else if is("POLYGON") || is("MULTILINESTRING") {
+        parse::<SkippedRings, T>(tokens, dim).map(drop)
+    } else if is("MULTIPOINT") {
+        parse::<SkippedMultiPoint, T>(tokens, dim).map(drop)
+    } else if is("MULTIPOLYGON") || is("POLYHEDRALSURFACE") {
+        parse::<SkippedPolygons, T>(tokens, dim).map(drop)
+    } else if is("GEOMETRYCOLLECTION") {
+        parse::<SkippedCollection, T>(tokens, dim).map(drop)
+    } else if is("TRIANGLE") {
+        parse::<SkippedTriangle, T>(tokens, dim).map(drop)
+    } else if is("TIN") {
+        parse::<SkippedTin, T>(tokens, dim).map(drop)
+    } else if is("CIRCULARSTRING") {
+        parse::<SkippedCircularString, T>(tokens, dim).map(drop)
+    } else if is("BOX") || is("BOX3D") {
+        let dim = match is("BOX") {
+            true => Dimension::XY,
+            false => Dimension::XYZ,
+        };
+        match tokens.peek() {
+            Some(Token::ParenOpen) => {
+                <SkippedBox as FromTokens<T>>::from_tokens_with_parens(tokens, dim).map(drop)
+            }
+            _ => Err(WktError::Syntax("Missing open parenthesis for type")),
+        }
+    } else if is_crs_keyword(keyword) {
+        Err(WktError::CrsWktNotSupported)
+    } else {
+        Err(WktError::Syntax("Invalid type encountered"))
+    }
+}
+
+impl<T> Wkt<T>
+where
+    T: WktFloat + FromStr + Default,
+{
+    /// Checks that `wkt_str` is valid WKT, failing with the same error as [`Wkt::from_str`],
+    /// without building the geometry. Coordinates are parsed and dropped, so that even
+    /// millions of them are checked without allocating.
+    pub fn validate_str(wkt_str: &str) -> Result<(), WktError> {
+        let input = wkt_str.strip_prefix('\u{FEFF}').unwrap_or(wkt_str);
+        if let Some((i, character)) = input.char_indices().find(|(_, c)| !c.is_ascii()) {
+            return Err(WktError::NonAscii {
+                character,
+                position: wkt_str.len() - input.len() + i,
+            });
+        }
+        let mut tokens = PeekableTokens::<T>::new(Tokens::from_str(input), WktParser::new());
+        let word = match tokens.next().transpose()? {
+            Some(Token::Word(word)) => word,
+            None => return Ok(()),
+            _ => return Err(WktError::Syntax("Invalid WKT format")),
+        };
+        skip_geometry(&word, &mut tokens)?;
+        match tokens.next().transpose()? {
+            Some(Token::Word(ref w)) if w.starts_with(';') => {
+                Err(WktError::Syntax("Unexpected trailing semicolon"))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::format;
+    use alloc::string::String;
+    use {Wkt, WktError};
+
+    fn long_linestring(points: usize) -> String {
+        let coords = (0..points)
+            .map(|i| format!("{} {}", i, i * 2))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("LINESTRING ({})", coords)
+    }
+
+    #[test]
+    fn validate_large_linestring() {
+        let wkt = long_linestring(100_000);
+        assert_eq!(Ok(()), Wkt::<f64>::validate_str(&wkt));
+
+        let malformed = wkt.replacen(", 3 6,", ", 3 6 ,,", 1);
+        assert_eq!(
+            Err(WktError::EmptyCoordinate),
+            Wkt::<f64>::validate_str(&malformed)
+        );
+        assert_eq!(
+            WktError::EmptyCoordinate,
+            Wkt::<f64>::from_str(&malformed).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn same_errors_as_from_str() {
+        for wkt in &[
+            "",
+            "POINT (1 2)",
+            "POINT Z (1 2 3)",
+            "POINTZ (1 2 3)",
+            "POINT (1)",
+            "POINT Z (1 2)",
+            "POINT (1 2",
+            "POINT 1 2",
+            "POINT EMPTY",
+            "LINESTRING (1 2, 3 4, )",
+            "POLYGON ((0 0, 1 0, 1 1, 0 0), (0 0 0, 1 1))",
+            "MULTIPOINT (1 2, (3 4))",
+            "MULTIPOINT ((1 2) (3 4))",
+            "MULTILINESTRING ((1 2, 3 4), EMPTY)",
+            "MULTIPOLYGON (((0 0, 1 0, 1 1, 0 0)), ((0 0, x)))",
+            "GEOMETRYCOLLECTION (POINT (1 2), LINESTRING (3 4, 5 6))",
+            "GEOMETRYCOLLECTION (POINT (1 2), 3)",
+            "GEOMETRYCOLLECTION (GEOMETRYCOLLECTION (CIRCLE (1 2)))",
+            "TRIANGLE ((0 0, 1 0, 0 1, 0 0))",
+            "TRIANGLE ((0 0, 1 0, 0 1, 1 1, 0 0))",
+            "TRIANGLE ((0 0, 1 0, 0 1, 0 0), (0 0, 1 0, 0 1, 0 0))",
+            "TIN (((0 0, 1 0, 0 1, 0 0)), ((0 0, 1 0, 0 0)))",
+            "POLYHEDRALSURFACE Z (((0 0 0, 0 1 0, 1 1 0, 0 0 0)))",
+            "CIRCULARSTRING (0 0, 1 1)",
+            "CIRCULARSTRING (0 0, 1 1, 2 0, 3 1)",
+            "CIRCULARSTRING (0 0, 1 1, 2 0)",
+            "BOX (0 0, 1 1)",
+            "BOX (0 0, 1 1, 2 2)",
+            "BOX3D (0 0 0, 1 1 1)",
+            "BOX 0 0",
+            "BOX ()",
+            "PROJCS[\"WGS 84 / UTM zone 33N\"]",
+            "POINT (1 2);",
+            "POINT (1 ñ)",
+            "10 20",
+        ] {
+            assert_eq!(
+                Wkt::<f64>::from_str(wkt).map(drop),
+                Wkt::<f64>::validate_str(wkt),
+                "{}",
+                wkt
+            );
+        }
+    }
+}
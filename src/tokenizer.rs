@@ -12,15 +12,32 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::iter::Peekable;
-use std::marker::PhantomData;
-use std::str;
-use WktFloat;
+//! The lexer behind the WKT parser, exposed to build parsers for other dialects.
+//!
+//! ```
+//! use wkt::tokenizer::{Token, Tokens};
+//!
+//! let tokens: Vec<Token<f64>> = Tokens::from_str("CIRCLE (1 2, 5)")
+//!     .collect::<Result<_, _>>()
+//!     .unwrap();
+//! assert_eq!(Token::Word("CIRCLE".to_string()), tokens[0]);
+//! assert_eq!(Token::ParenOpen, tokens[1]);
+//! assert_eq!(Token::Number(1.), tokens[2]);
+//! assert_eq!(Token::Comma, tokens[4]);
+//! ```
 
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use core::marker::PhantomData;
+use core::str;
+use {WktError, WktNum, WktParser};
+
+/// A lexical element of WKT. Keywords, and anything else which is not a number or
+/// punctuation, are words.
 #[derive(Debug, PartialEq)]
 pub enum Token<T>
 where
-    T: WktFloat,
+    T: WktNum,
 {
     Comma,
     Number(T),
@@ -30,10 +47,7 @@ where
 }
 
 fn is_whitespace(c: char) -> bool {
-    match c {
-        '\n' | '\r' | '\t' | ' ' => true,
-        _ => false,
-    }
+    matches!(c, '\n' | '\r' | '\t' | ' ')
 }
 
 fn is_numberlike(c: char) -> bool {
@@ -44,118 +58,246 @@ fn is_numberlike(c: char) -> bool {
     }
 }
 
-pub type PeekableTokens<'a, T> = Peekable<Tokens<'a, T>>;
+/// Spells a number so that every float type parses it alike: without a leading `+`, and
+/// with a `0` added to a dot without digits on one side, as in `.5` or `5.`. A dot or sign
+/// without any digit is left to fail.
+fn normalize_number(number: &str) -> Cow<'_, str> {
+    let number = number.trim_start_matches('+');
+    let mantissa_end = number.find(['e', 'E']).unwrap_or(number.len());
+    let (mantissa, exponent) = number.split_at(mantissa_end);
+    let digits = mantissa.trim_start_matches('-');
+    let leading = digits.starts_with('.');
+    let trailing = digits.ends_with('.');
+    if !(leading || trailing) || digits.len() < 2 {
+        return Cow::Borrowed(number);
+    }
+    let sign = &mantissa[..mantissa.len() - digits.len()];
+    let (before, after) = if leading { ("0", "") } else { ("", "0") };
+    Cow::Owned(format!("{}{}{}{}{}", sign, before, digits, after, exponent))
+}
+
+/// A token, or the error reading it, and the byte offset of its end
+type SpannedToken<T> = (Result<Token<T>, WktError>, usize);
+
+/// The token stream handed to the geometry parsers, along with the options of the
+/// [`WktParser`] driving them.
+pub struct PeekableTokens<'a, T>
+where
+    T: WktNum + str::FromStr + Default,
+{
+    tokens: Tokens<'a, T>,
+    /// The next token and the byte offset of its end, once peeked
+    peeked: Option<Option<SpannedToken<T>>>,
+    /// The byte offset of the end of the last token returned by `next`
+    consumed: usize,
+    options: WktParser,
+    depth: usize,
+    check_arity: bool,
+}
+
+impl<'a, T> PeekableTokens<'a, T>
+where
+    T: WktNum + str::FromStr + Default,
+{
+    /// Wraps `tokens`, parsing with `options`.
+    pub fn new(tokens: Tokens<'a, T>, options: WktParser) -> Self {
+        PeekableTokens {
+            tokens,
+            peeked: None,
+            consumed: 0,
+            options,
+            depth: 0,
+            check_arity: true,
+        }
+    }
+
+    /// The next token, without consuming it. A malformed token peeks as `None`, leaving
+    /// `next` to report the error.
+    pub fn peek(&mut self) -> Option<&Token<T>> {
+        if self.peeked.is_none() {
+            let next = self.tokens.next();
+            let end = self.tokens.offset();
+            self.peeked = Some(next.map(|token| (token, end)));
+        }
+        match self.peeked.as_ref()? {
+            Some((token, _)) => token.as_ref().ok(),
+            None => None,
+        }
+    }
+
+    /// The byte offset in the input of the end of the last token consumed.
+    pub(crate) fn consumed(&self) -> usize {
+        self.consumed
+    }
+
+    /// The options of the parser reading the tokens.
+    pub fn options(&self) -> &WktParser {
+        &self.options
+    }
+
+    /// Records entering a nested collection, failing beyond the configured maximum depth.
+    pub(crate) fn enter_collection(&mut self) -> Result<(), WktError> {
+        if self.depth >= self.options.max_depth {
+            return Err(WktError::MaxDepthExceeded);
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    pub(crate) fn exit_collection(&mut self) {
+        self.depth -= 1;
+    }
+
+    /// Whether coordinates are checked to have exactly as many ordinates as their
+    /// geometry's dimension, rather than leaving further numbers to the next coordinate
+    pub(crate) fn checks_arity(&self) -> bool {
+        self.check_arity
+    }
+
+    /// Enables or disables the ordinate count check, returning the previous setting.
+    pub(crate) fn set_check_arity(&mut self, enabled: bool) -> bool {
+        core::mem::replace(&mut self.check_arity, enabled)
+    }
+}
+
+impl<'a, T> Iterator for PeekableTokens<'a, T>
+where
+    T: WktNum + str::FromStr + Default,
+{
+    type Item = Result<Token<T>, WktError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = match self.peeked.take() {
+            Some(peeked) => peeked,
+            None => {
+                let next = self.tokens.next();
+                next.map(|token| (token, self.tokens.offset()))
+            }
+        };
+        let (token, end) = next?;
+        self.consumed = end;
+        Some(token)
+    }
+}
 
+/// An iterator over the tokens of a WKT string.
 #[derive(Debug)]
 pub struct Tokens<'a, T> {
-    chars: Peekable<str::Chars<'a>>,
+    /// The input not yet tokenized
+    rest: &'a str,
+    /// The length of the whole input
+    len: usize,
+    quoted_numbers: bool,
     phantom: PhantomData<T>,
 }
 
 impl<'a, T> Tokens<'a, T>
 where
-    T: WktFloat,
+    T: WktNum,
 {
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(input: &'a str) -> Self {
         Tokens {
-            chars: input.chars().peekable(),
+            rest: input,
+            len: input.len(),
+            quoted_numbers: false,
             phantom: PhantomData,
         }
     }
+
+    /// Reads double-quoted numbers such as `"1.5"` as numbers rather than words.
+    pub fn quoted_numbers(mut self, enabled: bool) -> Self {
+        self.quoted_numbers = enabled;
+        self
+    }
 }
 
 impl<'a, T> Iterator for Tokens<'a, T>
 where
-    T: WktFloat + str::FromStr + Default,
+    T: WktNum + str::FromStr + Default,
 {
-    type Item = Token<T>;
-
-    fn next(&mut self) -> Option<Token<T>> {
-        // TODO: should this return Result?
-        let mut next_char = self.chars.next()?;
+    type Item = Result<Token<T>, WktError>;
 
+    fn next(&mut self) -> Option<Self::Item> {
         // Skip whitespace
-        while is_whitespace(next_char) {
-            next_char = self.chars.next()?
-        }
+        let token_start = self.rest.trim_start_matches(is_whitespace);
+        let mut chars = token_start.chars();
+        let next_char = chars.next()?;
+        self.rest = chars.as_str();
 
         match next_char {
             '\0' => None,
-            '(' => Some(Token::ParenOpen),
-            ')' => Some(Token::ParenClose),
-            ',' => Some(Token::Comma),
+            '(' => Some(Ok(Token::ParenOpen)),
+            ')' => Some(Ok(Token::ParenClose)),
+            ',' => Some(Ok(Token::Comma)),
             c if is_numberlike(c) => {
-                let number = c.to_string() + &self.read_until_whitespace().unwrap_or_default();
-                match number.trim_start_matches('+').parse::<T>() {
-                    Ok(parsed_num) => Some(Token::Number(parsed_num)),
-                    Err(_) => None,
+                // Numbers are parsed from the input in place, as allocating for each of them
+                // dominates the parsing of long coordinate lists.
+                let len = c.len_utf8() + self.read_until_whitespace().len();
+                let number = &token_start[..len];
+                match normalize_number(number).parse::<T>() {
+                    Ok(parsed_num) => Some(Ok(Token::Number(parsed_num))),
+                    Err(_) => Some(Err(WktError::MalformedNumber(number.to_string()))),
+                }
+            }
+            '"' if self.quoted_numbers => {
+                let word = self.read_until_whitespace();
+                match word.strip_suffix('"').map(|number| number.parse::<T>()) {
+                    Some(Ok(parsed_num)) => Some(Ok(Token::Number(parsed_num))),
+                    _ => Some(Ok(Token::Word(format!("\"{}", word)))),
                 }
             }
             c => {
-                let word = c.to_string() + &self.read_until_whitespace().unwrap_or_default();
-                Some(Token::Word(word))
+                let len = c.len_utf8() + self.read_until_whitespace().len();
+                let word = &token_start[..len];
+                Some(Ok(Token::Word(word.to_string())))
             }
         }
     }
 }
 
-impl<'a, T> Tokens<'a, T>
-where
-    T: WktFloat + str::FromStr + Default,
-{
-    fn read_until_whitespace(&mut self) -> Option<String> {
-        let mut result = String::new();
-
-        while let Some(&next_char) = self.chars.peek() {
-            let marker = match next_char {
-                '\0' | '(' | ')' | ',' => true,
-                _ => false,
-            };
-
-            // Consume non-markers
-            if !marker {
-                let _ = self.chars.next();
-            }
-
-            let whitespace = is_whitespace(next_char);
-
-            // Append non-whitespace, non-marker characters
-            if !marker && !whitespace {
-                result.push(next_char);
-            }
-
-            // Stop reading when reached marker or whitespace
-            if marker || whitespace {
-                break;
-            }
-        }
+impl<'a, T> Tokens<'a, T> {
+    /// Consumes and returns the input up to the next whitespace or marker.
+    fn read_until_whitespace(&mut self) -> &'a str {
+        let end = self
+            .rest
+            .find(|c| matches!(c, '\0' | '(' | ')' | ',') || is_whitespace(c))
+            .unwrap_or(self.rest.len());
+        let (result, rest) = self.rest.split_at(end);
+        self.rest = rest;
+        result
+    }
 
-        if result.is_empty() {
-            None
-        } else {
-            Some(result)
-        }
+    /// The byte offset in the input of the end of the last token.
+    pub(crate) fn offset(&self) -> usize {
+        self.len - self.rest.len()
     }
 }
 
 #[test]
 fn test_tokenizer_empty() {
     let test_str = "";
-    let tokens: Vec<Token<f64>> = Tokens::from_str(test_str).collect();
+    let tokens: Vec<Token<f64>> = Tokens::from_str(test_str)
+        .collect::<Result<_, _>>()
+        .unwrap();
     assert_eq!(tokens, vec![]);
 }
 
 #[test]
 fn test_tokenizer_1word() {
     let test_str = "hello";
-    let tokens: Vec<Token<f64>> = Tokens::from_str(test_str).collect();
+    let tokens: Vec<Token<f64>> = Tokens::from_str(test_str)
+        .collect::<Result<_, _>>()
+        .unwrap();
     assert_eq!(tokens, vec![Token::Word("hello".to_string())]);
 }
 
 #[test]
 fn test_tokenizer_2words() {
     let test_str = "hello world";
-    let tokens: Vec<Token<f64>> = Tokens::from_str(test_str).collect();
+    let tokens: Vec<Token<f64>> = Tokens::from_str(test_str)
+        .collect::<Result<_, _>>()
+        .unwrap();
     assert_eq!(
         tokens,
         vec![
@@ -168,28 +310,74 @@ fn test_tokenizer_2words() {
 #[test]
 fn test_tokenizer_1number() {
     let test_str = "4.2";
-    let tokens: Vec<Token<f64>> = Tokens::from_str(test_str).collect();
+    let tokens: Vec<Token<f64>> = Tokens::from_str(test_str)
+        .collect::<Result<_, _>>()
+        .unwrap();
     assert_eq!(tokens, vec![Token::Number(4.2)]);
 }
 
 #[test]
 fn test_tokenizer_1number_plus() {
     let test_str = "+4.2";
-    let tokens: Vec<Token<f64>> = Tokens::from_str(test_str).collect();
+    let tokens: Vec<Token<f64>> = Tokens::from_str(test_str)
+        .collect::<Result<_, _>>()
+        .unwrap();
     assert_eq!(tokens, vec![Token::Number(4.2)]);
 }
 
 #[test]
 fn test_tokenizer_invalid_number() {
-    let test_str = "4.2p";
-    let tokens: Vec<Token<f64>> = Tokens::from_str(test_str).collect();
-    assert_eq!(tokens, vec![]);
+    let test_str = "4.2p 3";
+    let tokens: Vec<Result<Token<f64>, WktError>> = Tokens::from_str(test_str).collect();
+    assert_eq!(
+        tokens,
+        vec![
+            Err(WktError::MalformedNumber("4.2p".to_string())),
+            Ok(Token::Number(3.0)),
+        ]
+    );
+}
+
+#[test]
+fn test_tokenizer_dot_numbers() {
+    fn check<T: WktNum + str::FromStr + Default>(input: &str, expected: Option<T>) {
+        let tokens: Vec<Result<Token<T>, WktError>> = Tokens::from_str(input).collect();
+        match expected {
+            Some(n) => assert_eq!(vec![Ok(Token::Number(n))], tokens, "{}", input),
+            None => assert_eq!(
+                vec![Err(WktError::MalformedNumber(input.to_string()))],
+                tokens,
+                "{}",
+                input
+            ),
+        }
+    }
+
+    for &(input, expected) in &[
+        ("5.", Some(5.)),
+        ("-5.", Some(-5.)),
+        (".5", Some(0.5)),
+        ("-.5", Some(-0.5)),
+        ("+.5", Some(0.5)),
+        ("5.e1", Some(50.)),
+        (".5E-1", Some(0.05)),
+        (".", None),
+        ("-", None),
+        ("-.", None),
+        ("+", None),
+        ("..5", None),
+    ] {
+        check::<f64>(input, expected);
+        check::<f32>(input, expected.map(|n| n as f32));
+    }
 }
 
 #[test]
 fn test_tokenizer_2numbers() {
     let test_str = ".4 -2";
-    let tokens: Vec<Token<f64>> = Tokens::from_str(test_str).collect();
+    let tokens: Vec<Token<f64>> = Tokens::from_str(test_str)
+        .collect::<Result<_, _>>()
+        .unwrap();
     assert_eq!(tokens, vec![Token::Number(0.4), Token::Number(-2.0)]);
 }
 
@@ -197,12 +385,12 @@ fn test_tokenizer_2numbers() {
 fn test_no_stack_overflow() {
     fn check(c: &str, count: usize, expected: usize) {
         let test_str = c.repeat(count);
-        let tokens: Vec<Token<f64>> = Tokens::from_str(&test_str).collect();
+        let tokens: Vec<Result<Token<f64>, WktError>> = Tokens::from_str(&test_str).collect();
         assert_eq!(expected, tokens.len());
     }
 
     let count = 100_000;
-    check("+", count, 0);
+    check("+", count, 1);
     check(" ", count, 0);
     check("A", count, 1);
     check("1", count, 1);
@@ -211,10 +399,29 @@ fn test_no_stack_overflow() {
     check(",", count, count);
 }
 
+#[test]
+fn test_tokenizer_long_coordinate_list() {
+    let count = 200_000;
+    let test_str = (0..count)
+        .map(|i| format!("{}.5 -{}e-3", i, i))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let tokens: Vec<Token<f64>> = Tokens::from_str(&test_str)
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(3 * count - 1, tokens.len());
+    for (i, coord) in tokens.chunks(3).enumerate() {
+        assert_eq!(Token::Number(i as f64 + 0.5), coord[0]);
+        assert_eq!(Token::Number(-(i as f64) / 1000.), coord[1]);
+    }
+}
+
 #[test]
 fn test_tokenizer_point() {
     let test_str = "POINT (10 -20)";
-    let tokens: Vec<Token<f64>> = Tokens::from_str(test_str).collect();
+    let tokens: Vec<Token<f64>> = Tokens::from_str(test_str)
+        .collect::<Result<_, _>>()
+        .unwrap();
     assert_eq!(
         tokens,
         vec![
@@ -1,6 +1,7 @@
+use alloc::vec::Vec;
 use types::{
-    Coord, GeometryCollection, LineString, MultiLineString, MultiPoint, MultiPolygon, Point,
-    Polygon,
+    Coord, Dimension, GeometryCollection, LineString, MultiLineString, MultiPoint, MultiPolygon,
+    Point, Polygon,
 };
 use Geometry;
 use Wkt;
@@ -16,7 +17,7 @@ where
     fn to_wkt(&self) -> Wkt<T>;
 }
 
-fn g_point_to_w_coord<T>(g_point: &geo_types::Coordinate<T>) -> Coord<T>
+fn g_point_to_w_coord<T>(g_point: &geo_types::Coord<T>) -> Coord<T>
 where
     T: CoordFloat,
 {
@@ -33,10 +34,10 @@ where
     T: CoordFloat,
 {
     let coord = g_point_to_w_coord(&g_point.0);
-    Point(Some(coord))
+    Point(Some(coord), Dimension::XY)
 }
 
-fn g_points_to_w_coords<T>(g_points: &[geo_types::Coordinate<T>]) -> Vec<Coord<T>>
+fn g_points_to_w_coords<T>(g_points: &[geo_types::Coord<T>]) -> Vec<Coord<T>>
 where
     T: CoordFloat,
 {
@@ -51,7 +52,7 @@ where
         .iter()
         .map(|p| &p.0)
         .map(g_point_to_w_coord)
-        .map(|c| Point(Some(c)))
+        .map(|c| Point(Some(c), Dimension::XY))
         .collect()
 }
 
@@ -59,23 +60,23 @@ fn g_line_to_w_linestring<T>(g_line: &geo_types::Line<T>) -> LineString<T>
 where
     T: CoordFloat,
 {
-    g_points_to_w_linestring(&vec![g_line.start, g_line.end])
+    g_points_to_w_linestring(&[g_line.start, g_line.end])
 }
 
 fn g_linestring_to_w_linestring<T>(g_linestring: &geo_types::LineString<T>) -> LineString<T>
 where
     T: CoordFloat,
 {
-    let &geo_types::LineString(ref g_points) = g_linestring;
+    let geo_types::LineString(g_points) = g_linestring;
     g_points_to_w_linestring(g_points)
 }
 
-fn g_points_to_w_linestring<T>(g_coords: &[geo_types::Coordinate<T>]) -> LineString<T>
+fn g_points_to_w_linestring<T>(g_coords: &[geo_types::Coord<T>]) -> LineString<T>
 where
     T: CoordFloat,
 {
     let w_coords = g_points_to_w_coords(g_coords);
-    LineString(w_coords)
+    LineString(w_coords, Dimension::XY)
 }
 
 fn g_lines_to_w_lines<T>(g_lines: &[geo_types::LineString<T>]) -> Vec<LineString<T>>
@@ -84,7 +85,7 @@ where
 {
     let mut w_lines = vec![];
     for g_line in g_lines {
-        let &geo_types::LineString(ref g_points) = g_line;
+        let geo_types::LineString(g_points) = g_line;
         w_lines.push(g_points_to_w_linestring(g_points));
     }
     w_lines
@@ -115,34 +116,34 @@ where
     let mut poly_lines = vec![];
 
     // Outer
-    let &geo_types::LineString(ref outer_points) = outer_line;
+    let geo_types::LineString(outer_points) = outer_line;
     if !outer_points.is_empty() {
         poly_lines.push(g_points_to_w_linestring(outer_points));
     }
 
     // Inner
     let inner = g_lines_to_w_lines(inner_lines);
-    poly_lines.extend(inner.into_iter());
+    poly_lines.extend(inner);
 
-    Polygon(poly_lines)
+    Polygon(poly_lines, Dimension::XY)
 }
 
 fn g_mpoint_to_w_mpoint<T>(g_mpoint: &geo_types::MultiPoint<T>) -> MultiPoint<T>
 where
     T: CoordFloat,
 {
-    let &geo_types::MultiPoint(ref g_points) = g_mpoint;
+    let geo_types::MultiPoint(g_points) = g_mpoint;
     let w_points = g_points_to_w_points(g_points);
-    MultiPoint(w_points)
+    MultiPoint(w_points, Dimension::XY)
 }
 
 fn g_mline_to_w_mline<T>(g_mline: &geo_types::MultiLineString<T>) -> MultiLineString<T>
 where
     T: CoordFloat,
 {
-    let &geo_types::MultiLineString(ref g_lines) = g_mline;
+    let geo_types::MultiLineString(g_lines) = g_mline;
     let w_lines = g_lines_to_w_lines(g_lines);
-    MultiLineString(w_lines)
+    MultiLineString(w_lines, Dimension::XY)
 }
 
 fn g_polygons_to_w_polygons<T>(g_polygons: &[geo_types::Polygon<T>]) -> Vec<Polygon<T>>
@@ -160,54 +161,54 @@ fn g_mpolygon_to_w_mpolygon<T>(g_mpolygon: &geo_types::MultiPolygon<T>) -> Multi
 where
     T: CoordFloat,
 {
-    let &geo_types::MultiPolygon(ref g_polygons) = g_mpolygon;
+    let geo_types::MultiPolygon(g_polygons) = g_mpolygon;
     let w_polygons = g_polygons_to_w_polygons(g_polygons);
-    MultiPolygon(w_polygons)
+    MultiPolygon(w_polygons, Dimension::XY)
 }
 
 fn g_geocol_to_w_geocol<T>(g_geocol: &geo_types::GeometryCollection<T>) -> GeometryCollection<T>
 where
     T: CoordFloat,
 {
-    let &geo_types::GeometryCollection(ref g_geoms) = g_geocol;
+    let geo_types::GeometryCollection(g_geoms) = g_geocol;
     let mut w_geoms = vec![];
     for g_geom in g_geoms {
         let w_geom = g_geom_to_w_geom(g_geom);
         w_geoms.push(w_geom);
     }
-    GeometryCollection(w_geoms)
+    GeometryCollection(w_geoms, Dimension::XY)
 }
 
 fn g_geom_to_w_geom<T>(g_geom: &geo_types::Geometry<T>) -> Geometry<T>
 where
     T: CoordFloat,
 {
-    match g_geom {
-        &geo_types::Geometry::Point(ref g_point) => g_point_to_w_point(g_point).as_item(),
+    match *g_geom {
+        geo_types::Geometry::Point(ref g_point) => g_point_to_w_point(g_point).as_item(),
 
-        &geo_types::Geometry::Line(ref g_line) => g_line_to_w_linestring(g_line).as_item(),
+        geo_types::Geometry::Line(ref g_line) => g_line_to_w_linestring(g_line).as_item(),
 
-        &geo_types::Geometry::LineString(ref g_line) => {
+        geo_types::Geometry::LineString(ref g_line) => {
             g_linestring_to_w_linestring(g_line).as_item()
         }
 
-        &geo_types::Geometry::Triangle(ref g_triangle) => {
+        geo_types::Geometry::Triangle(ref g_triangle) => {
             g_triangle_to_w_polygon(g_triangle).as_item()
         }
 
-        &geo_types::Geometry::Rect(ref g_rect) => g_rect_to_w_polygon(g_rect).as_item(),
+        geo_types::Geometry::Rect(ref g_rect) => g_rect_to_w_polygon(g_rect).as_item(),
 
-        &geo_types::Geometry::Polygon(ref g_polygon) => g_polygon_to_w_polygon(g_polygon).as_item(),
+        geo_types::Geometry::Polygon(ref g_polygon) => g_polygon_to_w_polygon(g_polygon).as_item(),
 
-        &geo_types::Geometry::MultiPoint(ref g_mpoint) => g_mpoint_to_w_mpoint(g_mpoint).as_item(),
+        geo_types::Geometry::MultiPoint(ref g_mpoint) => g_mpoint_to_w_mpoint(g_mpoint).as_item(),
 
-        &geo_types::Geometry::MultiLineString(ref g_mline) => g_mline_to_w_mline(g_mline).as_item(),
+        geo_types::Geometry::MultiLineString(ref g_mline) => g_mline_to_w_mline(g_mline).as_item(),
 
-        &geo_types::Geometry::MultiPolygon(ref g_mpolygon) => {
+        geo_types::Geometry::MultiPolygon(ref g_mpolygon) => {
             g_mpolygon_to_w_mpolygon(g_mpolygon).as_item()
         }
 
-        &geo_types::Geometry::GeometryCollection(ref g_geocol) => {
+        geo_types::Geometry::GeometryCollection(ref g_geocol) => {
             g_geocol_to_w_geocol(g_geocol).as_item()
         }
     }
@@ -218,9 +219,12 @@ where
     T: CoordFloat,
 {
     fn to_wkt(&self) -> Wkt<T> {
-        let w_geom = g_geom_to_w_geom(&self);
+        let w_geom = g_geom_to_w_geom(self);
         Wkt {
             items: vec![w_geom],
+            comments: vec![None],
+            ids: vec![None],
+            spans: vec![None],
         }
     }
 }
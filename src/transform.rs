@@ -0,0 +1,391 @@
+// Copyright 2014-2021 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::vec::Vec;
+use approx::coord_eq;
+use types::{Coord, Dimension, GeometryCollection, LineString, MultiPolygon, PolyhedralSurface};
+use {Geometry, WktFloat};
+
+/// The guessed axis order of geographic coordinates, see [`Geometry::guess_axis_order`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AxisOrder {
+    /// Longitude first, as WKT expects
+    LonLat,
+    /// Latitude first, fixed with [`Geometry::swap_axes`]
+    LatLon,
+    /// Empty, projected or otherwise undecidable coordinates
+    Unknown,
+}
+
+impl<T> Geometry<T>
+where
+    T: WktFloat,
+{
+    /// Replaces every coordinate with the result of `f` and tags the geometry, and all of
+    /// its parts, with `new_dim`. The closure is expected to populate `z` and `m` to match
+    /// `new_dim`, for example to add elevations to a two dimensional line.
+    pub fn map_coords_with_dim<F>(mut self, new_dim: Dimension, f: F) -> Geometry<T>
+    where
+        F: Fn(Coord<T>) -> Coord<T>,
+    {
+        for coord in self.coords_mut() {
+            *coord = f(coord.clone());
+        }
+        self.set_dimension(new_dim);
+        self
+    }
+
+    /// Replaces every coordinate, including those of nested collections, with the result
+    /// of `f`, for example to flip, scale or reproject the geometry in place.
+    pub fn map_coords<F>(&mut self, mut f: F)
+    where
+        F: FnMut(Coord<T>) -> Coord<T>,
+    {
+        for coord in self.coords_mut() {
+            *coord = f(coord.clone());
+        }
+    }
+
+    /// A copy of the geometry with the `z` and `m` of every coordinate dropped and every
+    /// part tagged `XY`, for sinks which only accept two dimensional geometries.
+    pub fn to_2d(&self) -> Geometry<T> {
+        self.clone()
+            .map_coords_with_dim(Dimension::XY, |coord| Coord {
+                z: None,
+                m: None,
+                ..coord
+            })
+    }
+
+    /// A copy of the geometry with the `m` of every coordinate dropped, keeping any `z`.
+    /// Members of a geometry collection keep their own `Z` tag.
+    pub fn drop_measure(&self) -> Geometry<T> {
+        let without_m = |dim: Dimension| match dim.has_z() {
+            true => Dimension::XYZ,
+            false => Dimension::XY,
+        };
+        match self {
+            Geometry::GeometryCollection(collection) => {
+                Geometry::GeometryCollection(GeometryCollection(
+                    collection.0.iter().map(Geometry::drop_measure).collect(),
+                    without_m(collection.1),
+                ))
+            }
+            geometry => geometry.clone().map_coords_with_dim(
+                without_m(geometry.tagged_dimension()),
+                |coord| Coord { m: None, ..coord },
+            ),
+        }
+    }
+
+    /// Guesses from the coordinate ranges whether geographic coordinates were written
+    /// longitude first. An ordinate beyond ±90 can only be a longitude; otherwise the axis
+    /// reaching the larger magnitude is taken as the longitude, whose range is twice as
+    /// wide. This is a heuristic for messy data and can be wrong for small extents.
+    pub fn guess_axis_order(&self) -> AxisOrder {
+        let ninety = T::from(90).unwrap();
+        let limit = T::from(180).unwrap();
+        let mut max_x = None;
+        let mut max_y = None;
+        for coord in self.coords() {
+            let (x, y) = (coord.x.abs(), coord.y.abs());
+            if !(x <= limit && y <= limit) {
+                return AxisOrder::Unknown;
+            }
+            max_x = Some(max_x.map_or(x, |max: T| max.max(x)));
+            max_y = Some(max_y.map_or(y, |max: T| max.max(y)));
+        }
+
+        match (max_x, max_y) {
+            (Some(x), Some(y)) if x > ninety && y > ninety => AxisOrder::Unknown,
+            (Some(x), Some(y)) if x > y => AxisOrder::LonLat,
+            (Some(x), Some(y)) if y > x => AxisOrder::LatLon,
+            _ => AxisOrder::Unknown,
+        }
+    }
+
+    /// Swaps the x and y ordinates of every coordinate, to fix geometries written latitude
+    /// first.
+    pub fn swap_axes(&mut self) {
+        self.map_coords(|coord| Coord {
+            x: coord.y,
+            y: coord.x,
+            ..coord
+        });
+    }
+
+    /// Removes consecutive identical coordinates from every linestring and ring, descending
+    /// into collections. A closed ring stays closed. Circular strings are left alone, as
+    /// dropping a point would change their arcs.
+    pub fn dedup_coords(&mut self) {
+        self.dedup_coords_by(&|a: &Coord<T>, b: &Coord<T>| a == b);
+    }
+
+    /// Like [`Geometry::dedup_coords`], but also removes coordinates differing from the
+    /// previous one kept by at most `epsilon` in each ordinate.
+    pub fn dedup_coords_eps(&mut self, epsilon: T) {
+        self.dedup_coords_by(&|a: &Coord<T>, b: &Coord<T>| coord_eq(a, b, epsilon));
+    }
+
+    fn dedup_coords_by<F>(&mut self, eq: &F)
+    where
+        F: Fn(&Coord<T>, &Coord<T>) -> bool,
+    {
+        let rings = |rings: &mut [LineString<T>]| {
+            rings
+                .iter_mut()
+                .for_each(|ring| dedup_ring(&mut ring.0, eq))
+        };
+        match self {
+            Geometry::Point(_)
+            | Geometry::MultiPoint(_)
+            | Geometry::CircularString(_)
+            | Geometry::Box(_) => (),
+            Geometry::LineString(linestring) => linestring.0.dedup_by(|a, b| eq(a, b)),
+            Geometry::MultiLineString(multilinestring) => multilinestring
+                .0
+                .iter_mut()
+                .for_each(|linestring| linestring.0.dedup_by(|a, b| eq(a, b))),
+            Geometry::Polygon(polygon) => rings(&mut polygon.0),
+            Geometry::Triangle(triangle) => rings(&mut triangle.0),
+            Geometry::MultiPolygon(MultiPolygon(polygons, _))
+            | Geometry::PolyhedralSurface(PolyhedralSurface(polygons, _)) => polygons
+                .iter_mut()
+                .for_each(|polygon| rings(&mut polygon.0)),
+            Geometry::Tin(tin) => tin.0.iter_mut().for_each(|triangle| rings(&mut triangle.0)),
+            Geometry::GeometryCollection(collection) => collection
+                .0
+                .iter_mut()
+                .for_each(|geometry| geometry.dedup_coords_by(eq)),
+        }
+    }
+
+    pub(crate) fn set_dimension(&mut self, dim: Dimension) {
+        match self {
+            Geometry::Point(point) => point.1 = dim,
+            // The dimension of a box follows its corners.
+            Geometry::Box(_) => (),
+            Geometry::LineString(linestring) => linestring.1 = dim,
+            Geometry::CircularString(circularstring) => circularstring.1 = dim,
+            Geometry::Polygon(polygon) => {
+                polygon.0.iter_mut().for_each(|ring| ring.1 = dim);
+                polygon.1 = dim;
+            }
+            Geometry::Triangle(triangle) => {
+                triangle.0.iter_mut().for_each(|ring| ring.1 = dim);
+                triangle.1 = dim;
+            }
+            Geometry::MultiPoint(multipoint) => {
+                multipoint.0.iter_mut().for_each(|point| point.1 = dim);
+                multipoint.1 = dim;
+            }
+            Geometry::MultiLineString(multilinestring) => {
+                multilinestring.0.iter_mut().for_each(|line| line.1 = dim);
+                multilinestring.1 = dim;
+            }
+            Geometry::MultiPolygon(multipolygon) => {
+                for polygon in &mut multipolygon.0 {
+                    polygon.0.iter_mut().for_each(|ring| ring.1 = dim);
+                    polygon.1 = dim;
+                }
+                multipolygon.1 = dim;
+            }
+            Geometry::PolyhedralSurface(surface) => {
+                for polygon in &mut surface.0 {
+                    polygon.0.iter_mut().for_each(|ring| ring.1 = dim);
+                    polygon.1 = dim;
+                }
+                surface.1 = dim;
+            }
+            Geometry::Tin(tin) => {
+                for triangle in &mut tin.0 {
+                    triangle.0.iter_mut().for_each(|ring| ring.1 = dim);
+                    triangle.1 = dim;
+                }
+                tin.1 = dim;
+            }
+            Geometry::GeometryCollection(collection) => {
+                collection
+                    .0
+                    .iter_mut()
+                    .for_each(|geometry| geometry.set_dimension(dim));
+                collection.1 = dim;
+            }
+        }
+    }
+}
+
+/// Removes the consecutive duplicates of `ring`, keeping the closing coordinate of a closed
+/// ring in place of the duplicates preceding it.
+fn dedup_ring<T, F>(ring: &mut Vec<Coord<T>>, eq: &F)
+where
+    T: WktFloat,
+    F: Fn(&Coord<T>, &Coord<T>) -> bool,
+{
+    let closing = match (ring.first(), ring.last()) {
+        (Some(first), Some(last)) if ring.len() > 1 && first == last => last.clone(),
+        _ => return ring.dedup_by(|a, b| eq(a, b)),
+    };
+    ring.pop();
+    ring.dedup_by(|a, b| eq(a, b));
+    while ring.len() > 1 && eq(&ring[ring.len() - 1], &closing) {
+        ring.pop();
+    }
+    ring.push(closing);
+}
+
+#[cfg(test)]
+mod tests {
+    use transform::AxisOrder;
+    use types::{Coord, Dimension, Polygon, Triangle};
+    use {Geometry, Wkt};
+
+    #[test]
+    fn guess_axis_order() {
+        let mut wkt: Wkt<f64> = Wkt::from_str("POINT (40.7 -74.0)").unwrap();
+        assert_eq!(AxisOrder::LatLon, wkt.items[0].guess_axis_order());
+        wkt.items[0].swap_axes();
+        assert_eq!("POINT(-74 40.7)", wkt.items[0].to_string());
+        assert_eq!(AxisOrder::LonLat, wkt.items[0].guess_axis_order());
+
+        let wkt: Wkt<f64> = Wkt::from_str("LINESTRING (10 20, 170 -30)").unwrap();
+        assert_eq!(AxisOrder::LonLat, wkt.items[0].guess_axis_order());
+        let wkt: Wkt<f64> = Wkt::from_str("POINT (500000 4649776)").unwrap();
+        assert_eq!(AxisOrder::Unknown, wkt.items[0].guess_axis_order());
+        let wkt: Wkt<f64> = Wkt::from_str("POINT EMPTY").unwrap();
+        assert_eq!(AxisOrder::Unknown, wkt.items[0].guess_axis_order());
+    }
+
+    #[test]
+    fn negate_multipolygon_x() {
+        let mut wkt: Wkt<f64> =
+            Wkt::from_str("MULTIPOLYGON (((1 0, 2 0, 2 1, 1 0)), ((-1 5, -3 5, -3 6, -1 5)))")
+                .unwrap();
+        wkt.items[0].map_coords(|coord| Coord {
+            x: -coord.x,
+            ..coord
+        });
+        assert_eq!(
+            "MULTIPOLYGON(((-1 0,-2 0,-2 1,-1 0)),((1 5,3 5,3 6,1 5)))",
+            wkt.items[0].to_string()
+        );
+    }
+
+    #[test]
+    fn lift_point_to_3d() {
+        let mut wkt: Wkt<f64> = Wkt::from_str("POINT (1 2)").unwrap();
+        let point = wkt.items.pop().unwrap();
+        let lifted = point.map_coords_with_dim(Dimension::XYZ, |coord| Coord {
+            z: Some(coord.x + coord.y),
+            ..coord
+        });
+        assert_eq!(Ok(Dimension::XYZ), lifted.dimension());
+        assert_eq!("POINT Z(1 2 3)", lifted.to_string());
+    }
+
+    #[test]
+    fn drop_m_from_collection() {
+        let mut wkt: Wkt<f64> =
+            Wkt::from_str("GEOMETRYCOLLECTION M (POINT M (1 2 3), LINESTRING M EMPTY)").unwrap();
+        let collection = wkt.items.pop().unwrap();
+        let flattened =
+            collection.map_coords_with_dim(Dimension::XY, |coord| Coord { m: None, ..coord });
+        assert_eq!(Ok(Dimension::XY), flattened.dimension());
+        assert_eq!(
+            "GEOMETRYCOLLECTION(POINT(1 2),LINESTRING EMPTY)",
+            flattened.to_string()
+        );
+    }
+
+    #[test]
+    fn linestring_to_2d() {
+        let wkt: Wkt<f64> = Wkt::from_str("LINESTRING Z (1 2 3, 4 -5 6, 0 1 -2)").unwrap();
+        let flat = wkt.items[0].to_2d();
+        assert_eq!("LINESTRING(1 2,4 -5,0 1)", flat.to_string());
+        assert_eq!(Ok(Dimension::XY), flat.dimension());
+
+        let (min, max) = wkt.items[0].bounding_box().unwrap();
+        let (flat_min, flat_max) = flat.bounding_box().unwrap();
+        assert_eq!(
+            (min.x, min.y, max.x, max.y),
+            (flat_min.x, flat_min.y, flat_max.x, flat_max.y)
+        );
+        assert_eq!((None, None), (flat_min.z, flat_max.z));
+
+        let wkt: Wkt<f64> =
+            Wkt::from_str("GEOMETRYCOLLECTION (POINT ZM (1 2 3 4), POINT M (5 6 7))").unwrap();
+        assert_eq!(
+            "GEOMETRYCOLLECTION(POINT(1 2),POINT(5 6))",
+            wkt.items[0].to_2d().to_string()
+        );
+    }
+
+    #[test]
+    fn drop_measure() {
+        let wkt: Wkt<f64> =
+            Wkt::from_str("POLYGON ZM ((0 0 1 9, 1 0 1 9, 1 1 1 9, 0 0 1 9))").unwrap();
+        let dropped = wkt.items[0].drop_measure();
+        assert_eq!("POLYGON Z((0 0 1,1 0 1,1 1 1,0 0 1))", dropped.to_string());
+        assert_eq!(Ok(Dimension::XYZ), dropped.dimension());
+
+        let wkt: Wkt<f64> =
+            Wkt::from_str("GEOMETRYCOLLECTION (POINT ZM (1 2 3 4), POINT M (5 6 7))").unwrap();
+        assert_eq!(
+            "GEOMETRYCOLLECTION(POINT Z(1 2 3),POINT(5 6))",
+            wkt.items[0].drop_measure().to_string()
+        );
+    }
+
+    #[test]
+    fn dedup_linestring() {
+        let mut wkt: Wkt<f64> =
+            Wkt::from_str("LINESTRING (0 0, 0 0, 1 1, 1 1, 1 1, 2 2, 0 0)").unwrap();
+        wkt.items[0].dedup_coords();
+        assert_eq!("LINESTRING(0 0,1 1,2 2,0 0)", wkt.items[0].to_string());
+
+        let mut wkt: Wkt<f64> =
+            Wkt::from_str("MULTILINESTRING ((0 0, 0.001 0, 1 1, 1 1.001), (5 5, 5 5))").unwrap();
+        wkt.items[0].dedup_coords_eps(0.01);
+        assert_eq!("MULTILINESTRING((0 0,1 1),(5 5))", wkt.items[0].to_string());
+    }
+
+    #[test]
+    fn dedup_ring_keeps_closure() {
+        let mut wkt: Wkt<f64> = Wkt::from_str(
+            "GEOMETRYCOLLECTION (POLYGON ((0 0, 0 0, 4 0, 4 4, 4 4, 0 0, 0 0)), \
+             POLYGON ((0 0, 1 0, 1 0, 0 1, 0 0)))",
+        )
+        .unwrap();
+        if let Geometry::GeometryCollection(ref mut collection) = wkt.items[0] {
+            if let Geometry::Polygon(Polygon(rings, dim)) = collection.0.pop().unwrap() {
+                collection.push(Geometry::Triangle(Triangle(rings, dim)));
+            }
+        }
+        wkt.items[0].dedup_coords();
+        assert_eq!(
+            "GEOMETRYCOLLECTION(POLYGON((0 0,4 0,4 4,0 0)),TRIANGLE((0 0,1 0,0 1,0 0)))",
+            wkt.items[0].to_string()
+        );
+
+        // The closing coordinate is kept over the near duplicates preceding it.
+        let mut wkt: Wkt<f64> =
+            Wkt::from_str("POLYGON ((0 0, 4 0, 4 4, 0.001 0.001, 0 0))").unwrap();
+        wkt.items[0].dedup_coords_eps(0.01);
+        assert_eq!("POLYGON((0 0,4 0,4 4,0 0))", wkt.items[0].to_string());
+
+        let mut wkt: Wkt<f64> = Wkt::from_str("POLYGON ((1 1, 1 1, 1 1))").unwrap();
+        wkt.items[0].dedup_coords();
+        assert_eq!("POLYGON((1 1,1 1))", wkt.items[0].to_string());
+    }
+}
@@ -0,0 +1,210 @@
+// Copyright 2014-2021 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::fmt;
+use core::str::FromStr;
+use tokenizer::{PeekableTokens, Token};
+use types::coord::Coord;
+use types::dimension::Dimension;
+use types::linestring::LineString;
+use types::polygon::Polygon;
+use {FromTokens, Geometry, WktError, WktFloat, WktNum};
+
+/// A PostGIS extent, written `BOX(0 0, 2 3)`, or `BOX3D(0 0 0, 2 3 4)` when the corners
+/// carry a `z`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Box2D<T: WktNum> {
+    pub min: Coord<T>,
+    pub max: Coord<T>,
+}
+
+impl<T> Box2D<T>
+where
+    T: WktFloat,
+{
+    pub fn as_item(self) -> Geometry<T> {
+        Geometry::Box(self)
+    }
+
+    /// The box as a closed, counter-clockwise five coordinate polygon. The corners of a
+    /// `BOX3D` take the `z` of `min`, giving its bottom face.
+    pub fn to_polygon(&self) -> Polygon<T> {
+        let (min, max) = (&self.min, &self.max);
+        let corner = |x, y| Coord {
+            x,
+            y,
+            z: min.z,
+            m: None,
+        };
+        let ring = vec![
+            corner(min.x, min.y),
+            corner(max.x, min.y),
+            corner(max.x, max.y),
+            corner(min.x, max.y),
+            corner(min.x, min.y),
+        ];
+        let dim = self.dimension();
+        Polygon(vec![LineString(ring, dim)], dim)
+    }
+
+    /// `XYZ` for a `BOX3D`, `XY` otherwise
+    pub fn dimension(&self) -> Dimension {
+        if self.min.z.is_some() {
+            Dimension::XYZ
+        } else {
+            Dimension::XY
+        }
+    }
+}
+
+impl<T> Box2D<T>
+where
+    T: WktNum + FromStr + Default,
+{
+    /// Parses the parenthesized corners following `BOX` or `BOX3D`, which have no `EMPTY`
+    /// form.
+    pub(crate) fn from_tokens_with_corners(
+        tokens: &mut PeekableTokens<T>,
+        dim: Dimension,
+    ) -> Result<Self, WktError> {
+        // Not `from_tokens_with_parens`, whose `()` may mean `EMPTY`.
+        match tokens.next().transpose()? {
+            Some(Token::ParenOpen) => (),
+            _ => return Err(WktError::Syntax("Missing open parenthesis for type")),
+        }
+        let bbox = FromTokens::from_tokens(tokens, dim)?;
+        match tokens.next().transpose()? {
+            Some(Token::ParenClose) => Ok(bbox),
+            _ => Err(WktError::Syntax("Missing closing parenthesis for type")),
+        }
+    }
+}
+
+impl<T> fmt::Display for Box2D<T>
+where
+    T: WktNum + fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        let keyword = if self.min.z.is_some() { "BOX3D" } else { "BOX" };
+        write!(f, "{}({},{})", keyword, self.min, self.max)
+    }
+}
+
+impl<T> FromTokens<T> for Box2D<T>
+where
+    T: WktNum + FromStr + Default,
+{
+    fn from_tokens(tokens: &mut PeekableTokens<T>, dim: Dimension) -> Result<Self, WktError> {
+        if let Some(Token::ParenClose) = tokens.peek() {
+            return Err(WktError::WrongNumberOfCoordinates {
+                geometry: "Box",
+                expected: 2,
+                found: 0,
+            });
+        }
+        let mut corners =
+            FromTokens::comma_many(<Coord<T> as FromTokens<T>>::from_tokens, tokens, dim)?;
+        if corners.len() != 2 {
+            return Err(WktError::WrongNumberOfCoordinates {
+                geometry: "Box",
+                expected: 2,
+                found: corners.len(),
+            });
+        }
+        let max = corners.pop().unwrap();
+        let min = corners.pop().unwrap();
+        Ok(Box2D { min, max })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Box2D;
+    use types::Coord;
+    use {Geometry, Wkt, WktError, WktParser};
+
+    fn coord(x: f64, y: f64, z: Option<f64>) -> Coord<f64> {
+        Coord { x, y, z, m: None }
+    }
+
+    #[test]
+    fn basic_box() {
+        let wkt: Wkt<f64> = Wkt::from_str("BOX(0 0, 2 3)").unwrap();
+        match &wkt.items[0] {
+            Geometry::Box(bbox) => assert_eq!(
+                &Box2D {
+                    min: coord(0., 0., None),
+                    max: coord(2., 3., None),
+                },
+                bbox
+            ),
+            _ => unreachable!(),
+        }
+        assert_eq!("BOX(0 0,2 3)", wkt.items[0].to_string());
+        assert_eq!(
+            "POLYGON((0 0,2 0,2 3,0 3,0 0))",
+            match &wkt.items[0] {
+                Geometry::Box(bbox) => bbox.to_polygon().to_string(),
+                _ => unreachable!(),
+            }
+        );
+    }
+
+    #[test]
+    fn basic_box3d() {
+        let wkt: Wkt<f64> = Wkt::from_str("BOX3D(0 0 0, 2 3 4)").unwrap();
+        match &wkt.items[0] {
+            Geometry::Box(bbox) => assert_eq!(
+                &Box2D {
+                    min: coord(0., 0., Some(0.)),
+                    max: coord(2., 3., Some(4.)),
+                },
+                bbox
+            ),
+            _ => unreachable!(),
+        }
+        assert_eq!("BOX3D(0 0 0,2 3 4)", wkt.items[0].to_string());
+    }
+
+    #[test]
+    fn invalid_boxes() {
+        assert_eq!(
+            WktError::WrongNumberOfCoordinates {
+                geometry: "Box",
+                expected: 2,
+                found: 3,
+            },
+            <Wkt<f64>>::from_str("BOX(0 0, 2 3, 4 5)").unwrap_err()
+        );
+        assert_eq!(
+            WktError::WrongNumberOfCoordinates {
+                geometry: "Box",
+                expected: 2,
+                found: 1,
+            },
+            <Wkt<f64>>::from_str("BOX(0 0)").unwrap_err()
+        );
+        for input in &["BOX ()", "BOX3D ()"] {
+            let error = WktError::WrongNumberOfCoordinates {
+                geometry: "Box",
+                expected: 2,
+                found: 0,
+            };
+            assert_eq!(error, <Wkt<f64>>::from_str(input).unwrap_err());
+            assert_eq!(error, WktParser::lenient().parse::<f64>(input).unwrap_err());
+        }
+        <Wkt<f64>>::from_str("BOX EMPTY").err().unwrap();
+        <Wkt<f64>>::from_str("BOX3D(0 0, 2 3)").err().unwrap();
+    }
+}
@@ -0,0 +1,134 @@
+// Copyright 2014-2021 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+use format::write_empty;
+use tokenizer::PeekableTokens;
+use types::coord::Coord;
+use types::dimension::Dimension;
+use {FromTokens, Geometry, WktError, WktFloat, WktNum};
+
+/// A curve made of circular arcs. Each arc is defined by three coordinates, the last of
+/// which starts the next arc, so a non-empty circular string has an odd number of them.
+#[derive(Clone, Debug, Default)]
+pub struct CircularString<T: WktNum>(pub Vec<Coord<T>>, pub Dimension);
+
+impl<T> CircularString<T>
+where
+    T: WktFloat,
+{
+    pub fn as_item(self) -> Geometry<T> {
+        Geometry::CircularString(self)
+    }
+}
+
+impl<T> FromTokens<T> for CircularString<T>
+where
+    T: WktNum + FromStr + Default,
+{
+    fn new_empty(dim: Dimension) -> Self {
+        CircularString(vec![], dim)
+    }
+
+    fn from_tokens(tokens: &mut PeekableTokens<T>, dim: Dimension) -> Result<Self, WktError> {
+        let coords = FromTokens::comma_many(<Coord<T> as FromTokens<T>>::from_tokens, tokens, dim)?;
+        if coords.len() < 3 {
+            return Err(WktError::TooFewCoordinates {
+                geometry: "CircularString",
+                min: 3,
+                found: coords.len(),
+            });
+        }
+        if coords.len() % 2 == 0 {
+            return Err(WktError::Syntax(
+                "Expected an odd number of coordinates in CIRCULARSTRING",
+            ));
+        }
+        Ok(CircularString(coords, dim))
+    }
+}
+
+impl<T> fmt::Display for CircularString<T>
+where
+    T: WktNum + fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self.0.first() {
+            None => write_empty(f, "CIRCULARSTRING", self.1),
+            Some(coord) => {
+                let strings = self
+                    .0
+                    .iter()
+                    .map(|c| format!("{}", c))
+                    .collect::<Vec<_>>()
+                    .join(",");
+
+                match coord.dimension() {
+                    Dimension::XY => write!(f, "CIRCULARSTRING({})", strings),
+                    dim => write!(f, "CIRCULARSTRING {}({})", dim.tag(), strings),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CircularString;
+    use types::Dimension;
+    use {Geometry, Wkt, WktError};
+
+    #[test]
+    fn basic_circularstring() {
+        let mut wkt: Wkt<f64> = Wkt::from_str("CIRCULARSTRING (0 0, 1 1, 2 0)")
+            .ok()
+            .unwrap();
+        assert_eq!(1, wkt.items.len());
+        let coords = match wkt.items.pop().unwrap() {
+            Geometry::CircularString(CircularString(coords, Dimension::XY)) => coords,
+            _ => unreachable!(),
+        };
+        assert_eq!(3, coords.len());
+        assert_eq!(1.0, coords[1].y);
+    }
+
+    #[test]
+    fn invalid_circularstrings() {
+        <Wkt<f64>>::from_str("CIRCULARSTRING (0 0, 1 1, 2 0, 3 1)")
+            .err()
+            .unwrap();
+        assert_eq!(
+            WktError::TooFewCoordinates {
+                geometry: "CircularString",
+                min: 3,
+                found: 1,
+            },
+            <Wkt<f64>>::from_str("CIRCULARSTRING (0 0)").unwrap_err()
+        );
+    }
+
+    #[test]
+    fn write_circularstring() {
+        let wkt: Wkt<f64> = Wkt::from_str("CIRCULARSTRING (0 0, 1 1, 2 0, 3 -1, 4 0)").unwrap();
+        assert_eq!(
+            "CIRCULARSTRING(0 0,1 1,2 0,3 -1,4 0)",
+            format!("{}", wkt.items[0])
+        );
+
+        let empty: CircularString<f64> = CircularString(vec![], Dimension::XY);
+        assert_eq!("CIRCULARSTRING EMPTY", format!("{}", empty));
+    }
+}
@@ -12,25 +12,60 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::fmt;
-use std::str::FromStr;
+use core::fmt;
+use core::str::FromStr;
+use num_traits::PrimInt;
 use tokenizer::{PeekableTokens, Token};
-use {FromTokens, WktFloat};
+use types::dimension::Dimension;
+use {FromTokens, WktError, WktFloat, WktNum};
 
-#[derive(Clone, Debug, Default)]
-pub struct Coord<T>
-where
-    T: WktFloat,
-{
+/// A coordinate, with ordinates of any [`WktNum`]. Parse into an integer type directly
+/// for exact integral ordinates; [`Coord::to_integer`] converts coordinates already
+/// parsed as floats.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Coord<T> {
     pub x: T,
     pub y: T,
     pub z: Option<T>,
     pub m: Option<T>,
 }
 
+impl<T> Coord<T>
+where
+    T: WktFloat,
+{
+    /// Converts every ordinate to the integer type `I`, failing with
+    /// [`WktError::NotAnInteger`] if one is fractional or out of range for `I`. Integers
+    /// beyond 2^53 have already lost precision as `f64`; parse them as `I` instead.
+    pub fn to_integer<I: PrimInt>(&self) -> Result<Coord<I>, WktError> {
+        let convert = |value: T| match I::from(value) {
+            Some(integer) if value.fract().is_zero() => Ok(integer),
+            _ => Err(WktError::NotAnInteger(format!("{:?}", value))),
+        };
+        Ok(Coord {
+            x: convert(self.x)?,
+            y: convert(self.y)?,
+            z: self.z.map(convert).transpose()?,
+            m: self.m.map(convert).transpose()?,
+        })
+    }
+}
+
+impl<T> Coord<T> {
+    /// The dimension implied by which optional ordinates are set
+    pub fn dimension(&self) -> Dimension {
+        match (self.z.is_some(), self.m.is_some()) {
+            (false, false) => Dimension::XY,
+            (true, false) => Dimension::XYZ,
+            (false, true) => Dimension::XYM,
+            (true, true) => Dimension::XYZM,
+        }
+    }
+}
+
 impl<T> fmt::Display for Coord<T>
 where
-    T: WktFloat + fmt::Display,
+    T: WktNum + fmt::Display,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         write!(f, "{} {}", self.x, self.y)?;
@@ -46,26 +81,134 @@ where
 
 impl<T> FromTokens<T> for Coord<T>
 where
-    T: WktFloat + FromStr + Default,
+    T: WktNum + FromStr + Default,
 {
-    fn from_tokens(tokens: &mut PeekableTokens<T>) -> Result<Self, &'static str> {
-        let x = match tokens.next() {
-            Some(Token::Number(n)) => n,
-            _ => return Err("Expected a number for the X coordinate"),
+    fn from_tokens(tokens: &mut PeekableTokens<T>, dim: Dimension) -> Result<Self, WktError> {
+        let x = read_ordinate(tokens, "Expected a number for the X coordinate")?;
+        skip_comma(tokens);
+        let y = read_ordinate(tokens, "Expected a number for the Y coordinate")?;
+        if tokens.checks_arity() {
+            return read_with_arity(tokens, dim, x, y);
+        }
+        let z = if dim.has_z() {
+            Some(read_ordinate(
+                tokens,
+                "Expected a number for the Z coordinate",
+            )?)
+        } else {
+            None
         };
-        let y = match tokens.next() {
-            Some(Token::Number(n)) => n,
-            _ => return Err("Expected a number for the Y coordinate"),
+        let m = if dim.has_m() {
+            Some(read_ordinate(
+                tokens,
+                "Expected a number for the M coordinate",
+            )?)
+        } else {
+            None
         };
-        Ok(Coord {
-            x: x,
-            y: y,
-            z: None,
-            m: None,
-        })
+        Ok(Coord { x, y, z, m })
     }
 }
 
+/// Reads every ordinate following `x` and `y`, failing with
+/// [`WktError::InconsistentDimension`] unless there are as many as `dim` requires, so that
+/// `LINESTRING (10 20, 30 40 50)` is rejected.
+fn read_with_arity<T>(
+    tokens: &mut PeekableTokens<T>,
+    dim: Dimension,
+    x: T,
+    y: T,
+) -> Result<Coord<T>, WktError>
+where
+    T: WktNum + FromStr + Default,
+{
+    let expected = dim.has_z() as usize + dim.has_m() as usize;
+    let mut extra = [None, None];
+    let mut count = 0;
+    loop {
+        if count < expected {
+            skip_comma(tokens);
+        }
+        if !starts_ordinate(tokens.peek()) {
+            break;
+        }
+        let ordinate = read_ordinate(tokens, "Expected a number")?;
+        if count >= extra.len() {
+            return Err(WktError::Syntax("Expected at most four ordinates"));
+        }
+        extra[count] = Some(ordinate);
+        count += 1;
+    }
+
+    if count != expected {
+        let found = match count {
+            0 => Dimension::XY,
+            1 if dim == Dimension::XYM => Dimension::XYM,
+            1 => Dimension::XYZ,
+            _ => Dimension::XYZM,
+        };
+        return Err(WktError::InconsistentDimension {
+            expected: dim,
+            found,
+        });
+    }
+    let (z, m) = match dim {
+        Dimension::XY => (None, None),
+        Dimension::XYZ => (extra[0], None),
+        Dimension::XYM => (None, extra[0]),
+        Dimension::XYZM => (extra[0], extra[1]),
+    };
+    Ok(Coord { x, y, z, m })
+}
+
+/// Consumes a comma between two ordinates, if the parser accepts them.
+fn skip_comma<T>(tokens: &mut PeekableTokens<T>)
+where
+    T: WktNum + FromStr + Default,
+{
+    if tokens.options().commas_in_coordinates && tokens.peek() == Some(&Token::Comma) {
+        tokens.next();
+    }
+}
+
+/// Whether `token` is a number, or a word such as `NaN` which [`read_ordinate`] reads as
+/// one.
+fn starts_ordinate<T>(token: Option<&Token<T>>) -> bool
+where
+    T: WktNum + FromStr,
+{
+    match token {
+        Some(Token::Number(_)) => true,
+        Some(Token::Word(w)) => w.parse::<T>().is_ok(),
+        _ => false,
+    }
+}
+
+/// Reads a single ordinate, which must be finite unless the parser allows otherwise. `NaN`
+/// and `inf` are tokenized as words, so they are recognised here to report them as invalid
+/// numbers rather than as missing ones.
+fn read_ordinate<T>(tokens: &mut PeekableTokens<T>, missing: &'static str) -> Result<T, WktError>
+where
+    T: WktNum + FromStr + Default,
+{
+    let non_finite = tokens.options().non_finite;
+    match tokens.next().transpose()? {
+        Some(Token::Number(n)) if is_finite(n) || non_finite => Ok(n),
+        Some(Token::Number(n)) => Err(WktError::InvalidNumber(format!("{:?}", n))),
+        Some(Token::Word(ref w)) => match w.parse::<T>() {
+            Ok(n) if non_finite => Ok(n),
+            Ok(_) => Err(WktError::InvalidNumber(w.clone())),
+            Err(_) => Err(WktError::Syntax(missing)),
+        },
+        _ => Err(WktError::Syntax(missing)),
+    }
+}
+
+/// Whether `n` is neither NaN nor infinite, which integers always are.
+fn is_finite<T: WktNum>(n: T) -> bool {
+    n.to_f64().is_none_or(f64::is_finite)
+}
+
 #[cfg(test)]
 mod tests {
     use super::Coord;
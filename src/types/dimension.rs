@@ -0,0 +1,46 @@
+// Copyright 2014-2021 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// The ordinates carried by each coordinate of a geometry.
+///
+/// Every geometry records its dimension, so that an empty geometry such as
+/// `POINT Z EMPTY` keeps its tag.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Dimension {
+    #[default]
+    XY,
+    XYZ,
+    XYM,
+    XYZM,
+}
+
+impl Dimension {
+    pub fn has_z(self) -> bool {
+        matches!(self, Dimension::XYZ | Dimension::XYZM)
+    }
+
+    pub fn has_m(self) -> bool {
+        matches!(self, Dimension::XYM | Dimension::XYZM)
+    }
+
+    /// The WKT tag following the geometry keyword, empty for `XY`
+    pub fn tag(self) -> &'static str {
+        match self {
+            Dimension::XY => "",
+            Dimension::XYZ => "Z",
+            Dimension::XYM => "M",
+            Dimension::XYZM => "ZM",
+        }
+    }
+}
@@ -0,0 +1,86 @@
+// Copyright 2014-2021 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use core::str::FromStr;
+use types::{
+    Box2D, CircularString, GeometryCollection, GeometryType, LineString, MultiLineString,
+    MultiPoint, MultiPolygon, Point, Polygon, PolyhedralSurface, Tin, Triangle,
+};
+use {Geometry, Wkt, WktError, WktFloat};
+
+macro_rules! impl_from_wkt_str {
+    ($type:ident, $variant:ident) => {
+        impl<T> $type<T>
+        where
+            T: WktFloat + FromStr + Default,
+        {
+            #[doc = concat!(
+                        "Parses WKT which must be a `", stringify!($variant), "`, failing with ",
+                        "[`WktError::UnexpectedType`] for any other geometry."
+                    )]
+            pub fn from_wkt_str(wkt_str: &str) -> Result<Self, WktError> {
+                match Wkt::from_str(wkt_str)?.items.pop() {
+                    Some(Geometry::$variant(geometry)) => Ok(geometry),
+                    Some(other) => Err(WktError::UnexpectedType {
+                        expected: GeometryType::$variant,
+                        found: other.geometry_type(),
+                    }),
+                    None => Err(WktError::NoItems),
+                }
+            }
+        }
+    };
+}
+
+impl_from_wkt_str!(Point, Point);
+impl_from_wkt_str!(LineString, LineString);
+impl_from_wkt_str!(Polygon, Polygon);
+impl_from_wkt_str!(MultiPoint, MultiPoint);
+impl_from_wkt_str!(MultiLineString, MultiLineString);
+impl_from_wkt_str!(MultiPolygon, MultiPolygon);
+impl_from_wkt_str!(GeometryCollection, GeometryCollection);
+impl_from_wkt_str!(Triangle, Triangle);
+impl_from_wkt_str!(PolyhedralSurface, PolyhedralSurface);
+impl_from_wkt_str!(Tin, Tin);
+impl_from_wkt_str!(CircularString, CircularString);
+impl_from_wkt_str!(Box2D, Box);
+
+#[cfg(test)]
+mod tests {
+    use types::{Box2D, GeometryType, Point, Polygon};
+    use WktError;
+
+    #[test]
+    fn polygon_from_wkt_str() {
+        let polygon = Polygon::<f64>::from_wkt_str("POLYGON ((0 0, 1 0, 1 1, 0 0))").unwrap();
+        assert_eq!(1, polygon.0.len());
+        assert_eq!(
+            WktError::UnexpectedType {
+                expected: GeometryType::Polygon,
+                found: GeometryType::Point,
+            },
+            Polygon::<f64>::from_wkt_str("POINT (1 2)").unwrap_err()
+        );
+        assert_eq!(
+            WktError::NoItems,
+            Polygon::<f64>::from_wkt_str("").unwrap_err()
+        );
+    }
+
+    #[test]
+    fn box_from_wkt_str() {
+        let bbox = Box2D::<f64>::from_wkt_str("BOX (1 2, 3 4)").unwrap();
+        assert_eq!(3., bbox.max.x);
+        Point::<f64>::from_wkt_str("BOX (1 2, 3 4)").unwrap_err();
+    }
+}
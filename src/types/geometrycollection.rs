@@ -12,30 +12,50 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::fmt;
-use std::str::FromStr;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+use format::write_empty;
 use tokenizer::{PeekableTokens, Token};
-use {FromTokens, Geometry, WktFloat};
+use types::dimension::Dimension;
+use {FromTokens, Geometry, WktError, WktFloat, WktNum};
 
 #[derive(Clone, Debug, Default)]
-pub struct GeometryCollection<T: WktFloat>(pub Vec<Geometry<T>>);
+pub struct GeometryCollection<T: WktNum>(pub Vec<Geometry<T>>, pub Dimension);
 
 impl<T> GeometryCollection<T>
 where
     T: WktFloat,
 {
+    /// Builds a collection of `items`, tagged with the dimension of the first one.
+    pub fn new(items: Vec<Geometry<T>>) -> Self {
+        let dim = items
+            .first()
+            .map_or(Dimension::XY, Geometry::tagged_dimension);
+        GeometryCollection(items, dim)
+    }
+
     pub fn as_item(self) -> Geometry<T> {
         Geometry::GeometryCollection(self)
     }
+
+    /// Appends `item`. An empty collection takes the dimension of its first item, as with
+    /// [`GeometryCollection::new`].
+    pub fn push(&mut self, item: Geometry<T>) {
+        if self.0.is_empty() {
+            self.1 = item.tagged_dimension();
+        }
+        self.0.push(item);
+    }
 }
 
 impl<T> fmt::Display for GeometryCollection<T>
 where
-    T: WktFloat + fmt::Display,
+    T: WktNum + fmt::Display,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         if self.0.is_empty() {
-            f.write_str("GEOMETRYCOLLECTION EMPTY")
+            write_empty(f, "GEOMETRYCOLLECTION", self.1)
         } else {
             let strings = self
                 .0
@@ -44,48 +64,85 @@ where
                 .collect::<Vec<_>>()
                 .join(",");
 
-            write!(f, "GEOMETRYCOLLECTION({})", strings)
+            match self.1 {
+                Dimension::XY => write!(f, "GEOMETRYCOLLECTION({})", strings),
+                dim => write!(f, "GEOMETRYCOLLECTION {}({})", dim.tag(), strings),
+            }
         }
     }
 }
 
 impl<T> FromTokens<T> for GeometryCollection<T>
 where
-    T: WktFloat + FromStr + Default,
+    T: WktNum + FromStr + Default,
 {
-    fn from_tokens(tokens: &mut PeekableTokens<T>) -> Result<Self, &'static str> {
+    fn new_empty(dim: Dimension) -> Self {
+        GeometryCollection(vec![], dim)
+    }
+
+    fn from_tokens(tokens: &mut PeekableTokens<T>, dim: Dimension) -> Result<Self, WktError> {
+        tokens.enter_collection()?;
         let mut items = Vec::new();
 
-        let word = match tokens.next() {
+        let word = match tokens.next().transpose()? {
             Some(Token::Word(w)) => w,
-            _ => return Err("Expected a word in GEOMETRYCOLLECTION"),
+            _ => return Err(WktError::Syntax("Expected a word in GEOMETRYCOLLECTION")),
         };
 
-        let item = Geometry::from_word_and_tokens(&*word, tokens)?;
+        let item = Geometry::from_word_and_tokens(&word, tokens)?;
         items.push(item);
 
         while let Some(&Token::Comma) = tokens.peek() {
             tokens.next(); // throw away comma
 
-            let word = match tokens.next() {
+            let word = match tokens.next().transpose()? {
                 Some(Token::Word(w)) => w,
-                _ => return Err("Expected a word in GEOMETRYCOLLECTION"),
+                _ => return Err(WktError::Syntax("Expected a word in GEOMETRYCOLLECTION")),
             };
 
-            let item = Geometry::from_word_and_tokens(&*word, tokens)?;
+            let item = Geometry::from_word_and_tokens(&word, tokens)?;
             items.push(item);
         }
 
-        Ok(GeometryCollection(items))
+        tokens.exit_collection();
+        Ok(GeometryCollection(items, dim))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::GeometryCollection;
+    use types::Dimension;
     use types::*;
     use {Geometry, Wkt};
 
+    #[test]
+    fn push_points() {
+        let mut collection = GeometryCollection::<f64>::default();
+        assert_eq!("GEOMETRYCOLLECTION EMPTY", collection.to_string());
+        for (x, y) in [(1., 2.), (3., 4.)] {
+            let coord = Coord {
+                x,
+                y,
+                z: None,
+                m: None,
+            };
+            collection.push(Point(Some(coord), Dimension::XY).as_item());
+        }
+        assert_eq!(
+            "GEOMETRYCOLLECTION(POINT(1 2),POINT(3 4))",
+            collection.to_string()
+        );
+
+        let mut multilinestring = MultiLineString::<f64>::default();
+        multilinestring.push(LineString(vec![], Dimension::XYZ));
+        assert_eq!(Dimension::XYZ, multilinestring.1);
+        let mut multipolygon = MultiPolygon::<f64>::default();
+        multipolygon.push(Polygon(vec![], Dimension::XYM));
+        assert_eq!(1, multipolygon.0.len());
+        assert_eq!(Dimension::XYM, multipolygon.1);
+    }
+
     #[test]
     fn basic_geometrycollection() {
         let mut wkt: Wkt<f64> = Wkt::from_str("GEOMETRYCOLLECTION (POINT (8 4)))")
@@ -93,7 +150,7 @@ mod tests {
             .unwrap();
         assert_eq!(1, wkt.items.len());
         let items = match wkt.items.pop().unwrap() {
-            Geometry::GeometryCollection(GeometryCollection(items)) => items,
+            Geometry::GeometryCollection(GeometryCollection(items, Dimension::XY)) => items,
             _ => unreachable!(),
         };
         assert_eq!(1, items.len());
@@ -107,182 +164,281 @@ mod tests {
                 .unwrap();
         assert_eq!(1, wkt.items.len());
         let items = match wkt.items.pop().unwrap() {
-            Geometry::GeometryCollection(GeometryCollection(items)) => items,
+            Geometry::GeometryCollection(GeometryCollection(items, Dimension::XY)) => items,
             _ => unreachable!(),
         };
         assert_eq!(2, items.len());
     }
 
     #[test]
-    fn write_empty_geometry_collection() {
-        let geometry_collection: GeometryCollection<f64> = GeometryCollection(vec![]);
-
+    fn empty_items_keep_their_dimension() {
+        let wkt: Wkt<f64> =
+            Wkt::from_str("GEOMETRYCOLLECTION (POINT Z EMPTY, LINESTRING ZM EMPTY, POLYGON EMPTY)")
+                .unwrap();
         assert_eq!(
-            "GEOMETRYCOLLECTION EMPTY",
-            format!("{}", geometry_collection)
+            "GEOMETRYCOLLECTION(POINT Z EMPTY,LINESTRING ZM EMPTY,POLYGON EMPTY)",
+            wkt.items[0].to_string()
         );
     }
 
     #[test]
-    fn write_geometry_collection() {
-        let point = Geometry::Point(Point(Some(Coord {
-            x: 10.,
-            y: 20.,
-            z: None,
-            m: None,
-        })));
-
-        let multipoint = Geometry::MultiPoint(MultiPoint(vec![
-            Point(Some(Coord {
-                x: 10.1,
-                y: 20.2,
-                z: None,
-                m: None,
-            })),
-            Point(Some(Coord {
-                x: 30.3,
-                y: 40.4,
-                z: None,
-                m: None,
-            })),
-        ]));
-
-        let linestring = Geometry::LineString(LineString(vec![
-            Coord {
-                x: 10.,
-                y: 20.,
-                z: None,
-                m: None,
-            },
-            Coord {
-                x: 30.,
-                y: 40.,
-                z: None,
-                m: None,
-            },
-        ]));
-
-        let polygon = Geometry::Polygon(Polygon(vec![LineString(vec![
-            Coord {
-                x: 0.,
-                y: 0.,
-                z: None,
-                m: None,
-            },
-            Coord {
-                x: 20.,
-                y: 40.,
+    fn new_geometry_collection() {
+        let point = Point(
+            Some(Coord {
+                x: 1.,
+                y: 2.,
                 z: None,
                 m: None,
-            },
-            Coord {
-                x: 40.,
-                y: 0.,
-                z: None,
-                m: None,
-            },
-            Coord {
-                x: 0.,
-                y: 0.,
-                z: None,
-                m: None,
-            },
-        ])]));
-
-        let multilinestring = Geometry::MultiLineString(MultiLineString(vec![
-            LineString(vec![
-                Coord {
-                    x: 10.1,
-                    y: 20.2,
-                    z: None,
-                    m: None,
-                },
-                Coord {
-                    x: 30.3,
-                    y: 40.4,
-                    z: None,
-                    m: None,
-                },
-            ]),
-            LineString(vec![
+            }),
+            Dimension::XY,
+        );
+        let linestring = LineString(
+            vec![
                 Coord {
-                    x: 50.5,
-                    y: 60.6,
+                    x: 3.,
+                    y: 4.,
                     z: None,
                     m: None,
                 },
                 Coord {
-                    x: 70.7,
-                    y: 80.8,
+                    x: 5.,
+                    y: 6.,
                     z: None,
                     m: None,
                 },
-            ]),
-        ]));
+            ],
+            Dimension::XY,
+        );
+        let collection = GeometryCollection::new(vec![point.as_item(), linestring.as_item()]);
+        assert_eq!(
+            "GEOMETRYCOLLECTION(POINT(1 2),LINESTRING(3 4,5 6))",
+            collection.to_string()
+        );
+        assert_eq!(
+            "GEOMETRYCOLLECTION EMPTY",
+            GeometryCollection::<f64>::new(vec![]).to_string()
+        );
+    }
 
-        let multipolygon = Geometry::MultiPolygon(MultiPolygon(vec![
-            Polygon(vec![LineString(vec![
-                Coord {
-                    x: 0.,
-                    y: 0.,
-                    z: None,
-                    m: None,
-                },
-                Coord {
-                    x: 20.,
-                    y: 40.,
-                    z: None,
-                    m: None,
-                },
-                Coord {
-                    x: 40.,
-                    y: 0.,
-                    z: None,
-                    m: None,
-                },
-                Coord {
-                    x: 0.,
-                    y: 0.,
-                    z: None,
-                    m: None,
-                },
-            ])]),
-            Polygon(vec![LineString(vec![
-                Coord {
-                    x: 40.,
-                    y: 40.,
-                    z: None,
-                    m: None,
-                },
-                Coord {
-                    x: 20.,
-                    y: 45.,
-                    z: None,
-                    m: None,
-                },
+    #[test]
+    fn write_empty_geometry_collection() {
+        let geometry_collection: GeometryCollection<f64> =
+            GeometryCollection(vec![], Dimension::XY);
+
+        assert_eq!(
+            "GEOMETRYCOLLECTION EMPTY",
+            format!("{}", geometry_collection)
+        );
+    }
+
+    #[test]
+    fn write_geometry_collection() {
+        let point = Geometry::Point(Point(
+            Some(Coord {
+                x: 10.,
+                y: 20.,
+                z: None,
+                m: None,
+            }),
+            Dimension::XY,
+        ));
+
+        let multipoint = Geometry::MultiPoint(MultiPoint(
+            vec![
+                Point(
+                    Some(Coord {
+                        x: 10.1,
+                        y: 20.2,
+                        z: None,
+                        m: None,
+                    }),
+                    Dimension::XY,
+                ),
+                Point(
+                    Some(Coord {
+                        x: 30.3,
+                        y: 40.4,
+                        z: None,
+                        m: None,
+                    }),
+                    Dimension::XY,
+                ),
+            ],
+            Dimension::XY,
+        ));
+
+        let linestring = Geometry::LineString(LineString(
+            vec![
                 Coord {
-                    x: 45.,
-                    y: 30.,
+                    x: 10.,
+                    y: 20.,
                     z: None,
                     m: None,
                 },
                 Coord {
-                    x: 40.,
+                    x: 30.,
                     y: 40.,
                     z: None,
                     m: None,
                 },
-            ])]),
-        ]));
-
-        let geometrycollection = GeometryCollection(vec![
-            point,
-            multipoint,
-            linestring,
-            polygon,
-            multilinestring,
-            multipolygon,
-        ]);
+            ],
+            Dimension::XY,
+        ));
+
+        let polygon = Geometry::Polygon(Polygon(
+            vec![LineString(
+                vec![
+                    Coord {
+                        x: 0.,
+                        y: 0.,
+                        z: None,
+                        m: None,
+                    },
+                    Coord {
+                        x: 20.,
+                        y: 40.,
+                        z: None,
+                        m: None,
+                    },
+                    Coord {
+                        x: 40.,
+                        y: 0.,
+                        z: None,
+                        m: None,
+                    },
+                    Coord {
+                        x: 0.,
+                        y: 0.,
+                        z: None,
+                        m: None,
+                    },
+                ],
+                Dimension::XY,
+            )],
+            Dimension::XY,
+        ));
+
+        let multilinestring = Geometry::MultiLineString(MultiLineString(
+            vec![
+                LineString(
+                    vec![
+                        Coord {
+                            x: 10.1,
+                            y: 20.2,
+                            z: None,
+                            m: None,
+                        },
+                        Coord {
+                            x: 30.3,
+                            y: 40.4,
+                            z: None,
+                            m: None,
+                        },
+                    ],
+                    Dimension::XY,
+                ),
+                LineString(
+                    vec![
+                        Coord {
+                            x: 50.5,
+                            y: 60.6,
+                            z: None,
+                            m: None,
+                        },
+                        Coord {
+                            x: 70.7,
+                            y: 80.8,
+                            z: None,
+                            m: None,
+                        },
+                    ],
+                    Dimension::XY,
+                ),
+            ],
+            Dimension::XY,
+        ));
+
+        let multipolygon = Geometry::MultiPolygon(MultiPolygon(
+            vec![
+                Polygon(
+                    vec![LineString(
+                        vec![
+                            Coord {
+                                x: 0.,
+                                y: 0.,
+                                z: None,
+                                m: None,
+                            },
+                            Coord {
+                                x: 20.,
+                                y: 40.,
+                                z: None,
+                                m: None,
+                            },
+                            Coord {
+                                x: 40.,
+                                y: 0.,
+                                z: None,
+                                m: None,
+                            },
+                            Coord {
+                                x: 0.,
+                                y: 0.,
+                                z: None,
+                                m: None,
+                            },
+                        ],
+                        Dimension::XY,
+                    )],
+                    Dimension::XY,
+                ),
+                Polygon(
+                    vec![LineString(
+                        vec![
+                            Coord {
+                                x: 40.,
+                                y: 40.,
+                                z: None,
+                                m: None,
+                            },
+                            Coord {
+                                x: 20.,
+                                y: 45.,
+                                z: None,
+                                m: None,
+                            },
+                            Coord {
+                                x: 45.,
+                                y: 30.,
+                                z: None,
+                                m: None,
+                            },
+                            Coord {
+                                x: 40.,
+                                y: 40.,
+                                z: None,
+                                m: None,
+                            },
+                        ],
+                        Dimension::XY,
+                    )],
+                    Dimension::XY,
+                ),
+            ],
+            Dimension::XY,
+        ));
+
+        let geometrycollection = GeometryCollection(
+            vec![
+                point,
+                multipoint,
+                linestring,
+                polygon,
+                multilinestring,
+                multipolygon,
+            ],
+            Dimension::XY,
+        );
 
         assert_eq!(
             "GEOMETRYCOLLECTION(\
@@ -0,0 +1,174 @@
+// Copyright 2014-2021 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use bounding_box::expand;
+use core::iter;
+use types::{
+    Box2D, CircularString, Coord, Dimension, GeometryCollection, LineString, MultiLineString,
+    MultiPoint, MultiPolygon, Point, Polygon, PolyhedralSurface, Tin, Triangle,
+};
+use {Geometry, WktFloat};
+
+/// The operations shared by the geometry types, for code generic over any of them.
+///
+/// ```
+/// use wkt::types::GeometryTrait;
+///
+/// fn min_x<G: GeometryTrait<f64>>(geometry: &G) -> Option<f64> {
+///     geometry.bounding_box().map(|(min, _)| min.x)
+/// }
+/// ```
+pub trait GeometryTrait<T>
+where
+    T: WktFloat,
+{
+    /// Wraps the geometry in the [`Geometry`] enum.
+    #[allow(clippy::wrong_self_convention)]
+    fn as_item(self) -> Geometry<T>;
+
+    /// The dimension the geometry is tagged with.
+    fn dimension(&self) -> Dimension;
+
+    /// The corners of the axis-aligned box enclosing the geometry, see
+    /// [`Geometry::bounding_box`].
+    fn bounding_box(&self) -> Option<(Coord<T>, Coord<T>)>;
+}
+
+macro_rules! impl_geometry_trait {
+    ($type:ident, $geometry:ident => $coords:expr) => {
+        impl<T> GeometryTrait<T> for $type<T>
+        where
+            T: WktFloat,
+        {
+            fn as_item(self) -> Geometry<T> {
+                Geometry::$type(self)
+            }
+
+            fn dimension(&self) -> Dimension {
+                self.1
+            }
+
+            fn bounding_box(&self) -> Option<(Coord<T>, Coord<T>)> {
+                let $geometry = self;
+                $coords.fold(None, expand)
+            }
+        }
+    };
+}
+
+impl_geometry_trait!(Point, point => point.0.iter());
+impl_geometry_trait!(LineString, linestring => linestring.0.iter());
+impl_geometry_trait!(CircularString, circularstring => circularstring.0.iter());
+impl_geometry_trait!(Polygon, polygon => polygon.0.iter().flat_map(|ring| &ring.0));
+impl_geometry_trait!(Triangle, triangle => triangle.0.iter().flat_map(|ring| &ring.0));
+impl_geometry_trait!(MultiPoint, multipoint => multipoint.0.iter().flat_map(|point| &point.0));
+impl_geometry_trait!(
+    MultiLineString,
+    multilinestring => multilinestring.0.iter().flat_map(|line| &line.0)
+);
+impl_geometry_trait!(
+    MultiPolygon,
+    multipolygon => multipolygon
+        .0
+        .iter()
+        .flat_map(|polygon| &polygon.0)
+        .flat_map(|ring| &ring.0)
+);
+impl_geometry_trait!(
+    PolyhedralSurface,
+    surface => surface
+        .0
+        .iter()
+        .flat_map(|polygon| &polygon.0)
+        .flat_map(|ring| &ring.0)
+);
+impl_geometry_trait!(
+    Tin,
+    tin => tin
+        .0
+        .iter()
+        .flat_map(|triangle| &triangle.0)
+        .flat_map(|ring| &ring.0)
+);
+impl_geometry_trait!(
+    GeometryCollection,
+    collection => collection.0.iter().flat_map(Geometry::coords)
+);
+
+impl<T> GeometryTrait<T> for Box2D<T>
+where
+    T: WktFloat,
+{
+    fn as_item(self) -> Geometry<T> {
+        Geometry::Box(self)
+    }
+
+    fn dimension(&self) -> Dimension {
+        Box2D::dimension(self)
+    }
+
+    fn bounding_box(&self) -> Option<(Coord<T>, Coord<T>)> {
+        iter::once(&self.min)
+            .chain(iter::once(&self.max))
+            .fold(None, expand)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GeometryTrait;
+    use types::{Coord, Dimension, Point};
+    use {Geometry, Wkt};
+
+    fn extent<G: GeometryTrait<f64>>(geometry: G) -> Option<(f64, f64, f64, f64)> {
+        let (min, max) = geometry.bounding_box()?;
+        Some((min.x, min.y, max.x, max.y))
+    }
+
+    #[test]
+    fn generic_bounding_box() {
+        let mut wkt: Wkt<f64> = Wkt::from_str(
+            "GEOMETRYCOLLECTION (POLYGON ((0 0, 4 0, 4 3, 0 0)), MULTIPOINT ((-1 5), (2 2)))",
+        )
+        .unwrap();
+        let collection = match wkt.items.pop().unwrap() {
+            Geometry::GeometryCollection(collection) => collection,
+            _ => unreachable!(),
+        };
+        for item in collection.0.iter().cloned() {
+            let expected = item
+                .bounding_box()
+                .map(|(min, max)| (min.x, min.y, max.x, max.y));
+            match item {
+                Geometry::Polygon(polygon) => assert_eq!(expected, extent(polygon)),
+                Geometry::MultiPoint(multipoint) => assert_eq!(expected, extent(multipoint)),
+                _ => unreachable!(),
+            }
+        }
+        assert_eq!(Some((-1., 0., 4., 5.)), extent(collection));
+
+        let point = Point(
+            Some(Coord {
+                x: 1.,
+                y: 2.,
+                z: None,
+                m: None,
+            }),
+            Dimension::XY,
+        );
+        assert_eq!(Dimension::XY, point.dimension());
+        assert_eq!(Some((1., 2., 1., 2.)), extent(point.clone()));
+        assert_eq!("POINT(1 2)", point.as_item().to_string());
+        assert_eq!(None, extent(Point::<f64>(None, Dimension::XY)));
+    }
+}
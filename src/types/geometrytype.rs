@@ -0,0 +1,69 @@
+// Copyright 2014-2021 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// The kind of a [`Geometry`](crate::Geometry), without its coordinates.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GeometryType {
+    Point,
+    LineString,
+    Polygon,
+    MultiPoint,
+    MultiLineString,
+    MultiPolygon,
+    GeometryCollection,
+    Triangle,
+    PolyhedralSurface,
+    Tin,
+    CircularString,
+    Box,
+}
+
+impl GeometryType {
+    /// The type name, such as `"LineString"`
+    pub fn name(self) -> &'static str {
+        match self {
+            GeometryType::Point => "Point",
+            GeometryType::LineString => "LineString",
+            GeometryType::Polygon => "Polygon",
+            GeometryType::MultiPoint => "MultiPoint",
+            GeometryType::MultiLineString => "MultiLineString",
+            GeometryType::MultiPolygon => "MultiPolygon",
+            GeometryType::GeometryCollection => "GeometryCollection",
+            GeometryType::Triangle => "Triangle",
+            GeometryType::PolyhedralSurface => "PolyhedralSurface",
+            GeometryType::Tin => "Tin",
+            GeometryType::CircularString => "CircularString",
+            GeometryType::Box => "Box",
+        }
+    }
+
+    /// The uppercase WKT keyword, such as `"LINESTRING"`. A box is `"BOX"`, even though
+    /// one with a `z` is written `BOX3D`.
+    pub fn keyword(self) -> &'static str {
+        match self {
+            GeometryType::Point => "POINT",
+            GeometryType::LineString => "LINESTRING",
+            GeometryType::Polygon => "POLYGON",
+            GeometryType::MultiPoint => "MULTIPOINT",
+            GeometryType::MultiLineString => "MULTILINESTRING",
+            GeometryType::MultiPolygon => "MULTIPOLYGON",
+            GeometryType::GeometryCollection => "GEOMETRYCOLLECTION",
+            GeometryType::Triangle => "TRIANGLE",
+            GeometryType::PolyhedralSurface => "POLYHEDRALSURFACE",
+            GeometryType::Tin => "TIN",
+            GeometryType::CircularString => "CIRCULARSTRING",
+            GeometryType::Box => "BOX",
+        }
+    }
+}
@@ -12,14 +12,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::fmt;
-use std::str::FromStr;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+use format::write_empty;
 use tokenizer::PeekableTokens;
 use types::coord::Coord;
-use {FromTokens, Geometry, WktFloat};
+use types::dimension::Dimension;
+use {FromTokens, Geometry, WktError, WktFloat, WktNum};
 
 #[derive(Clone, Debug, Default)]
-pub struct LineString<T: WktFloat>(pub Vec<Coord<T>>);
+pub struct LineString<T: WktNum>(pub Vec<Coord<T>>, pub Dimension);
 
 impl<T> LineString<T>
 where
@@ -28,34 +31,110 @@ where
     pub fn as_item(self) -> Geometry<T> {
         Geometry::LineString(self)
     }
+
+    /// Returns `true` if the first and last coordinates are equal. An empty linestring
+    /// is not closed.
+    pub fn is_closed(&self) -> bool {
+        match (self.0.first(), self.0.last()) {
+            (Some(first), Some(last)) => first == last,
+            _ => false,
+        }
+    }
+
+    /// Inserts evenly spaced coordinates so that no segment is longer than `max_len` in the
+    /// plane, interpolating `z` and `m`. A `max_len` which is not positive leaves the line
+    /// unchanged.
+    pub fn densify(&self, max_len: T) -> LineString<T> {
+        let mut coords = Vec::with_capacity(self.densified_len(max_len));
+        for segment in self.0.windows(2) {
+            let (start, end) = (&segment[0], &segment[1]);
+            let pieces = pieces(start, end, max_len);
+            let n = T::from(pieces).unwrap();
+            coords.push(start.clone());
+            for i in 1..pieces {
+                let t = T::from(i).unwrap() / n;
+                let lerp = |a: T, b: T| a + (b - a) * t;
+                coords.push(Coord {
+                    x: lerp(start.x, end.x),
+                    y: lerp(start.y, end.y),
+                    z: start.z.zip(end.z).map(|(a, b)| lerp(a, b)),
+                    m: start.m.zip(end.m).map(|(a, b)| lerp(a, b)),
+                });
+            }
+        }
+        coords.extend(self.0.last().cloned());
+        LineString(coords, self.1)
+    }
+
+    /// The number of coordinates [`LineString::densify`] would produce with `max_len`,
+    /// without building the line.
+    pub fn densified_len(&self, max_len: T) -> usize {
+        let added: usize = self
+            .0
+            .windows(2)
+            .map(|segment| pieces(&segment[0], &segment[1], max_len) - 1)
+            .sum();
+        self.0.len() + added
+    }
+
+    /// Splits the line at the coordinate `index` into two lines which both contain it.
+    /// Returns `None` if `index` is the first or last coordinate or out of range, which
+    /// would leave a piece of fewer than two coordinates.
+    pub fn split_at(&self, index: usize) -> Option<(LineString<T>, LineString<T>)> {
+        if index == 0 || index + 1 >= self.0.len() {
+            return None;
+        }
+        Some((
+            LineString(self.0[..=index].to_vec(), self.1),
+            LineString(self.0[index..].to_vec(), self.1),
+        ))
+    }
+}
+
+/// The number of pieces the segment from `start` to `end` is split into by densifying.
+fn pieces<T: WktFloat>(start: &Coord<T>, end: &Coord<T>, max_len: T) -> usize {
+    let len = (end.x - start.x).hypot(end.y - start.y);
+    if max_len > T::zero() && len > max_len {
+        (len / max_len).ceil().to_usize().unwrap_or(1)
+    } else {
+        1
+    }
 }
 
 impl<T> FromTokens<T> for LineString<T>
 where
-    T: WktFloat + FromStr + Default,
+    T: WktNum + FromStr + Default,
 {
-    fn from_tokens(tokens: &mut PeekableTokens<T>) -> Result<Self, &'static str> {
-        let result = FromTokens::comma_many(<Coord<T> as FromTokens<T>>::from_tokens, tokens);
-        result.map(LineString)
+    fn new_empty(dim: Dimension) -> Self {
+        LineString(vec![], dim)
+    }
+
+    fn from_tokens(tokens: &mut PeekableTokens<T>, dim: Dimension) -> Result<Self, WktError> {
+        let result = FromTokens::comma_many(<Coord<T> as FromTokens<T>>::from_tokens, tokens, dim);
+        result.map(|x| LineString(x, dim))
     }
 }
 
 impl<T> fmt::Display for LineString<T>
 where
-    T: WktFloat + fmt::Display,
+    T: WktNum + fmt::Display,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        if self.0.is_empty() {
-            f.write_str("LINESTRING EMPTY")
-        } else {
-            let strings = self
-                .0
-                .iter()
-                .map(|c| format!("{}", c))
-                .collect::<Vec<_>>()
-                .join(",");
-
-            write!(f, "LINESTRING({})", strings)
+        match self.0.first() {
+            None => write_empty(f, "LINESTRING", self.1),
+            Some(coord) => {
+                let strings = self
+                    .0
+                    .iter()
+                    .map(|c| format!("{}", c))
+                    .collect::<Vec<_>>()
+                    .join(",");
+
+                match coord.dimension() {
+                    Dimension::XY => write!(f, "LINESTRING({})", strings),
+                    dim => write!(f, "LINESTRING {}({})", dim.tag(), strings),
+                }
+            }
         }
     }
 }
@@ -63,14 +142,15 @@ where
 #[cfg(test)]
 mod tests {
     use super::{Coord, LineString};
-    use {Geometry, Wkt};
+    use types::Dimension;
+    use {Geometry, Wkt, WktError};
 
     #[test]
     fn basic_linestring() {
-        let mut wkt = Wkt::from_str("LINESTRING (10 -20, -0 -0.5)").ok().unwrap();
+        let mut wkt: Wkt<f64> = Wkt::from_str("LINESTRING (10 -20, -0 -0.5)").ok().unwrap();
         assert_eq!(1, wkt.items.len());
         let coords = match wkt.items.pop().unwrap() {
-            Geometry::LineString(LineString(coords)) => coords,
+            Geometry::LineString(LineString(coords, Dimension::XY)) => coords,
             _ => unreachable!(),
         };
         assert_eq!(2, coords.len());
@@ -86,29 +166,93 @@ mod tests {
         assert_eq!(None, coords[1].m);
     }
 
+    #[test]
+    fn mixed_dimension_linestring() {
+        assert_eq!(
+            WktError::InconsistentDimension {
+                expected: Dimension::XY,
+                found: Dimension::XYZ,
+            },
+            <Wkt<f64>>::from_str("LINESTRING (10 20, 30 40 50)").unwrap_err()
+        );
+        assert_eq!(
+            WktError::InconsistentDimension {
+                expected: Dimension::XYZ,
+                found: Dimension::XY,
+            },
+            <Wkt<f64>>::from_str("LINESTRING Z (10 20 5, 30 40)").unwrap_err()
+        );
+
+        let wkt: Wkt<f64> = Wkt::from_str("LINESTRING Z (10 20 5, 30 40 50)").unwrap();
+        assert_eq!(Ok(Dimension::XYZ), wkt.items[0].dimension());
+    }
+
+    #[test]
+    fn densify_linestring() {
+        let wkt: Wkt<f64> = Wkt::from_str("LINESTRING Z (0 0 0, 10 0 10, 11 0 10)").unwrap();
+        let linestring = match &wkt.items[0] {
+            Geometry::LineString(linestring) => linestring,
+            _ => unreachable!(),
+        };
+        let densified = linestring.densify(3.);
+        assert_eq!(6, linestring.densified_len(3.));
+        assert_eq!(densified.0.len(), linestring.densified_len(3.));
+        assert_eq!(
+            "LINESTRING Z(0 0 0,2.5 0 2.5,5 0 5,7.5 0 7.5,10 0 10,11 0 10)",
+            densified.to_string()
+        );
+        assert_eq!(3, linestring.densified_len(0.));
+        assert_eq!(
+            0,
+            LineString::<f64>(vec![], Dimension::XY).densified_len(3.)
+        );
+    }
+
+    #[test]
+    fn split_linestring() {
+        let wkt: Wkt<f64> = Wkt::from_str("LINESTRING (0 0, 1 0, 2 0, 3 1)").unwrap();
+        let linestring = match &wkt.items[0] {
+            Geometry::LineString(linestring) => linestring,
+            _ => unreachable!(),
+        };
+        let (first, second) = linestring.split_at(2).unwrap();
+        assert_eq!("LINESTRING(0 0,1 0,2 0)", first.to_string());
+        assert_eq!("LINESTRING(2 0,3 1)", second.to_string());
+        let (first, second) = linestring.split_at(1).unwrap();
+        assert_eq!("LINESTRING(0 0,1 0)", first.to_string());
+        assert_eq!("LINESTRING(1 0,2 0,3 1)", second.to_string());
+
+        assert!(linestring.split_at(0).is_none());
+        assert!(linestring.split_at(3).is_none());
+        assert!(linestring.split_at(4).is_none());
+    }
+
     #[test]
     fn write_empty_linestring() {
-        let linestring: LineString<f64> = LineString(vec![]);
+        let linestring: LineString<f64> = LineString(vec![], Dimension::XY);
 
         assert_eq!("LINESTRING EMPTY", format!("{}", linestring));
     }
 
     #[test]
     fn write_linestring() {
-        let linestring = LineString(vec![
-            Coord {
-                x: 10.1,
-                y: 20.2,
-                z: None,
-                m: None,
-            },
-            Coord {
-                x: 30.3,
-                y: 40.4,
-                z: None,
-                m: None,
-            },
-        ]);
+        let linestring = LineString(
+            vec![
+                Coord {
+                    x: 10.1,
+                    y: 20.2,
+                    z: None,
+                    m: None,
+                },
+                Coord {
+                    x: 30.3,
+                    y: 40.4,
+                    z: None,
+                    m: None,
+                },
+            ],
+            Dimension::XY,
+        );
 
         assert_eq!("LINESTRING(10.1 20.2,30.3 40.4)", format!("{}", linestring));
     }
@@ -12,20 +12,37 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub use self::bbox::Box2D;
+pub use self::circularstring::CircularString;
 pub use self::coord::Coord;
+pub use self::dimension::Dimension;
 pub use self::geometrycollection::GeometryCollection;
+pub use self::geometrytrait::GeometryTrait;
+pub use self::geometrytype::GeometryType;
 pub use self::linestring::LineString;
 pub use self::multilinestring::MultiLineString;
 pub use self::multipoint::MultiPoint;
 pub use self::multipolygon::MultiPolygon;
 pub use self::point::Point;
-pub use self::polygon::Polygon;
+pub use self::polygon::{Polygon, Winding};
+pub use self::polyhedralsurface::PolyhedralSurface;
+pub use self::tin::Tin;
+pub use self::triangle::Triangle;
 
+mod bbox;
+mod circularstring;
 mod coord;
+mod dimension;
+mod fromwktstr;
 mod geometrycollection;
+mod geometrytrait;
+mod geometrytype;
 mod linestring;
 mod multilinestring;
 mod multipoint;
 mod multipolygon;
 mod point;
 mod polygon;
+mod polyhedralsurface;
+mod tin;
+mod triangle;
@@ -12,14 +12,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::fmt;
-use std::str::FromStr;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+use format::write_empty;
 use tokenizer::PeekableTokens;
+use types::coord::Coord;
+use types::dimension::Dimension;
 use types::linestring::LineString;
-use {FromTokens, Geometry, WktFloat};
+use {FromTokens, Geometry, WktError, WktFloat, WktNum};
 
 #[derive(Clone, Debug, Default)]
-pub struct MultiLineString<T: WktFloat>(pub Vec<LineString<T>>);
+pub struct MultiLineString<T: WktNum>(pub Vec<LineString<T>>, pub Dimension);
 
 impl<T> MultiLineString<T>
 where
@@ -28,43 +32,102 @@ where
     pub fn as_item(self) -> Geometry<T> {
         Geometry::MultiLineString(self)
     }
+
+    /// Appends `linestring`. An empty collection takes the dimension of its first line.
+    pub fn push(&mut self, linestring: LineString<T>) {
+        if self.0.is_empty() {
+            self.1 = linestring.1;
+        }
+        self.0.push(linestring);
+    }
+
+    /// Greedily stitches lines sharing an endpoint into longer lines, reversing lines
+    /// where needed. Lines which touch no other line are kept as they are.
+    pub fn merge_contiguous(&self) -> MultiLineString<T> {
+        let mut merged: Vec<Vec<Coord<T>>> = Vec::new();
+        for line in &self.0 {
+            let mut current = line.0.clone();
+            while let Some(i) = merged.iter().position(|other| touches(&current, other)) {
+                current = join(current, merged.remove(i));
+            }
+            merged.push(current);
+        }
+        MultiLineString(
+            merged
+                .into_iter()
+                .map(|coords| LineString(coords, self.1))
+                .collect(),
+            self.1,
+        )
+    }
+}
+
+fn touches<T: WktFloat>(a: &[Coord<T>], b: &[Coord<T>]) -> bool {
+    match (a.first(), a.last(), b.first(), b.last()) {
+        (Some(a_first), Some(a_last), Some(b_first), Some(b_last)) => {
+            a_last == b_first || b_last == a_first || a_last == b_last || a_first == b_first
+        }
+        _ => false,
+    }
+}
+
+/// Joins two touching lines at their shared endpoint.
+fn join<T: WktFloat>(mut a: Vec<Coord<T>>, mut b: Vec<Coord<T>>) -> Vec<Coord<T>> {
+    if a.first() == b.last() {
+        core::mem::swap(&mut a, &mut b);
+    } else if a.first() == b.first() {
+        a.reverse();
+    } else if a.last() == b.last() {
+        b.reverse();
+    }
+    a.extend(b.into_iter().skip(1));
+    a
 }
 
 impl<T> fmt::Display for MultiLineString<T>
 where
-    T: WktFloat + fmt::Display,
+    T: WktNum + fmt::Display,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        if self.0.is_empty() {
-            f.write_str("MULTILINESTRING EMPTY")
-        } else {
-            let strings = self
-                .0
-                .iter()
-                .map(|l| {
-                    l.0.iter()
-                        .map(|c| format!("{} {}", c.x, c.y))
-                        .collect::<Vec<_>>()
-                        .join(",")
-                })
-                .collect::<Vec<_>>()
-                .join("),(");
-
-            write!(f, "MULTILINESTRING(({}))", strings)
+        match self.0.iter().flat_map(|l| l.0.iter()).next() {
+            None => write_empty(f, "MULTILINESTRING", self.1),
+            Some(coord) => {
+                let strings = self
+                    .0
+                    .iter()
+                    .map(|l| {
+                        l.0.iter()
+                            .map(|c| format!("{}", c))
+                            .collect::<Vec<_>>()
+                            .join(",")
+                    })
+                    .collect::<Vec<_>>()
+                    .join("),(");
+
+                match coord.dimension() {
+                    Dimension::XY => write!(f, "MULTILINESTRING(({}))", strings),
+                    dim => write!(f, "MULTILINESTRING {}(({}))", dim.tag(), strings),
+                }
+            }
         }
     }
 }
 
 impl<T> FromTokens<T> for MultiLineString<T>
 where
-    T: WktFloat + FromStr + Default,
+    T: WktNum + FromStr + Default,
 {
-    fn from_tokens(tokens: &mut PeekableTokens<T>) -> Result<Self, &'static str> {
+    fn new_empty(dim: Dimension) -> Self {
+        MultiLineString(vec![], dim)
+    }
+
+    fn from_tokens(tokens: &mut PeekableTokens<T>, dim: Dimension) -> Result<Self, WktError> {
         let result = FromTokens::comma_many(
             <LineString<T> as FromTokens<T>>::from_tokens_with_parens,
             tokens,
+            dim,
         );
-        result.map(MultiLineString)
+        result.map(|x| MultiLineString(x, dim))
     }
 }
 
@@ -72,6 +135,7 @@ where
 mod tests {
     use super::{LineString, MultiLineString};
     use types::Coord;
+    use types::Dimension;
     use {Geometry, Wkt};
 
     #[test]
@@ -81,51 +145,88 @@ mod tests {
             .unwrap();
         assert_eq!(1, wkt.items.len());
         let lines = match wkt.items.pop().unwrap() {
-            Geometry::MultiLineString(MultiLineString(lines)) => lines,
+            Geometry::MultiLineString(MultiLineString(lines, Dimension::XY)) => lines,
             _ => unreachable!(),
         };
         assert_eq!(2, lines.len());
     }
 
+    #[test]
+    fn merge_contiguous() {
+        let mut wkt: Wkt<f64> = Wkt::from_str("MULTILINESTRING ((0 0, 1 1), (1 1, 2 1))").unwrap();
+        let multilinestring = match wkt.items.pop().unwrap() {
+            Geometry::MultiLineString(multilinestring) => multilinestring,
+            _ => unreachable!(),
+        };
+        assert_eq!(
+            "MULTILINESTRING((0 0,1 1,2 1))",
+            multilinestring.merge_contiguous().to_string()
+        );
+    }
+
+    #[test]
+    fn merge_contiguous_reversed_and_disjoint() {
+        let mut wkt: Wkt<f64> =
+            Wkt::from_str("MULTILINESTRING ((1 1, 2 1), (5 5, 6 6), (3 0, 2 1), (1 1, 0 0))")
+                .unwrap();
+        let multilinestring = match wkt.items.pop().unwrap() {
+            Geometry::MultiLineString(multilinestring) => multilinestring,
+            _ => unreachable!(),
+        };
+        assert_eq!(
+            "MULTILINESTRING((5 5,6 6),(3 0,2 1,1 1,0 0))",
+            multilinestring.merge_contiguous().to_string()
+        );
+    }
+
     #[test]
     fn write_empty_multilinestring() {
-        let multilinestring: MultiLineString<f64> = MultiLineString(vec![]);
+        let multilinestring: MultiLineString<f64> = MultiLineString(vec![], Dimension::XY);
 
         assert_eq!("MULTILINESTRING EMPTY", format!("{}", multilinestring));
     }
 
     #[test]
     fn write_multilinestring() {
-        let multilinestring = MultiLineString(vec![
-            LineString(vec![
-                Coord {
-                    x: 10.1,
-                    y: 20.2,
-                    z: None,
-                    m: None,
-                },
-                Coord {
-                    x: 30.3,
-                    y: 40.4,
-                    z: None,
-                    m: None,
-                },
-            ]),
-            LineString(vec![
-                Coord {
-                    x: 50.5,
-                    y: 60.6,
-                    z: None,
-                    m: None,
-                },
-                Coord {
-                    x: 70.7,
-                    y: 80.8,
-                    z: None,
-                    m: None,
-                },
-            ]),
-        ]);
+        let multilinestring = MultiLineString(
+            vec![
+                LineString(
+                    vec![
+                        Coord {
+                            x: 10.1,
+                            y: 20.2,
+                            z: None,
+                            m: None,
+                        },
+                        Coord {
+                            x: 30.3,
+                            y: 40.4,
+                            z: None,
+                            m: None,
+                        },
+                    ],
+                    Dimension::XY,
+                ),
+                LineString(
+                    vec![
+                        Coord {
+                            x: 50.5,
+                            y: 60.6,
+                            z: None,
+                            m: None,
+                        },
+                        Coord {
+                            x: 70.7,
+                            y: 80.8,
+                            z: None,
+                            m: None,
+                        },
+                    ],
+                    Dimension::XY,
+                ),
+            ],
+            Dimension::XY,
+        );
 
         assert_eq!(
             "MULTILINESTRING((10.1 20.2,30.3 40.4),(50.5 60.6,70.7 80.8))",
@@ -12,14 +12,19 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::fmt;
-use std::str::FromStr;
-use tokenizer::PeekableTokens;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+use format::write_empty;
+use tokenizer::{PeekableTokens, Token};
+use types::coord::Coord;
+use types::dimension::Dimension;
 use types::point::Point;
-use {FromTokens, Geometry, WktFloat};
+use {FromTokens, Geometry, WktError, WktFloat, WktNum};
 
 #[derive(Clone, Debug, Default)]
-pub struct MultiPoint<T: WktFloat>(pub Vec<Point<T>>);
+pub struct MultiPoint<T: WktNum>(pub Vec<Point<T>>, pub Dimension);
 
 impl<T> MultiPoint<T>
 where
@@ -28,44 +33,150 @@ where
     pub fn as_item(self) -> Geometry<T> {
         Geometry::MultiPoint(self)
     }
+
+    /// Approximates the two dimensional point minimising the sum of distances to all
+    /// points with `iterations` steps of Weiszfeld's algorithm, starting from the
+    /// centroid. Unlike the centroid it is robust to outliers. Returns `None` if there are
+    /// no points.
+    pub fn geometric_median(&self, iterations: usize) -> Option<Coord<T>> {
+        let coords: Vec<&Coord<T>> = self.0.iter().filter_map(|p| p.0.as_ref()).collect();
+        if coords.is_empty() {
+            return None;
+        }
+        let count = T::from(coords.len())?;
+        let mut x = coords.iter().fold(T::zero(), |sum, c| sum + c.x) / count;
+        let mut y = coords.iter().fold(T::zero(), |sum, c| sum + c.y) / count;
+        for _ in 0..iterations {
+            let (mut sum_x, mut sum_y, mut sum_weights) = (T::zero(), T::zero(), T::zero());
+            for coord in &coords {
+                let distance = (coord.x - x).hypot(coord.y - y);
+                if distance <= T::epsilon() {
+                    // The estimate sits on an input point, where the update is undefined.
+                    continue;
+                }
+                sum_x = sum_x + coord.x / distance;
+                sum_y = sum_y + coord.y / distance;
+                sum_weights = sum_weights + distance.recip();
+            }
+            if sum_weights.is_zero() {
+                break;
+            }
+            x = sum_x / sum_weights;
+            y = sum_y / sum_weights;
+        }
+        Some(Coord {
+            x,
+            y,
+            z: None,
+            m: None,
+        })
+    }
 }
 
-impl<T> fmt::Display for MultiPoint<T>
+impl<T> MultiPoint<T>
 where
-    T: WktFloat + fmt::Display,
+    T: WktNum + fmt::Display,
 {
-    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+    /// Formats the multipoint in the bare form `MULTIPOINT(1 2,3 4)` some consumers
+    /// expect, rather than the `MULTIPOINT((1 2),(3 4))` of its `Display` impl.
+    pub fn to_wkt_bare(&self) -> String {
+        Bare(self).to_string()
+    }
+
+    fn write(&self, f: &mut fmt::Formatter, bare: bool) -> fmt::Result {
         if self.0.is_empty() {
-            f.write_str("MULTIPOINT EMPTY")
-        } else {
-            let strings = self
-                .0
-                .iter()
-                .filter_map(|p| p.0.as_ref())
-                .map(|c| format!("({} {})", c.x, c.y))
-                .collect::<Vec<_>>()
-                .join(",");
-
-            write!(f, "MULTIPOINT({})", strings)
+            return write_empty(f, "MULTIPOINT", self.1);
+        }
+        let strings = self
+            .0
+            .iter()
+            .filter_map(|p| p.0.as_ref())
+            .map(|c| match bare {
+                true => format!("{}", c),
+                false => format!("({})", c),
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+
+        let first = self.0.iter().filter_map(|p| p.0.as_ref()).next();
+        match first.map_or(self.1, |coord| coord.dimension()) {
+            Dimension::XY => write!(f, "MULTIPOINT({})", strings),
+            dim => write!(f, "MULTIPOINT {}({})", dim.tag(), strings),
         }
     }
 }
 
+/// Displays a multipoint in the bare form of [`MultiPoint::to_wkt_bare`]
+struct Bare<'a, T: WktNum>(&'a MultiPoint<T>);
+
+impl<'a, T> fmt::Display for Bare<'a, T>
+where
+    T: WktNum + fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        self.0.write(f, true)
+    }
+}
+
+impl<T> fmt::Display for MultiPoint<T>
+where
+    T: WktNum + fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        self.write(f, false)
+    }
+}
+
 impl<T> FromTokens<T> for MultiPoint<T>
 where
-    T: WktFloat + FromStr + Default,
+    T: WktNum + FromStr + Default,
 {
-    fn from_tokens(tokens: &mut PeekableTokens<T>) -> Result<Self, &'static str> {
-        let result =
-            FromTokens::comma_many(<Point<T> as FromTokens<T>>::from_tokens_with_parens, tokens);
-        result.map(MultiPoint)
+    fn new_empty(dim: Dimension) -> Self {
+        MultiPoint(vec![], dim)
+    }
+
+    fn from_tokens(tokens: &mut PeekableTokens<T>, dim: Dimension) -> Result<Self, WktError> {
+        let without_commas = dim == Dimension::XY && tokens.options().multipoint_without_commas;
+        // Without commas, further numbers begin the next point rather than adding ordinates.
+        let check_arity = tokens.set_check_arity(!without_commas);
+        let points = read_points(tokens, dim, without_commas);
+        tokens.set_check_arity(check_arity);
+        Ok(MultiPoint(points?, dim))
     }
 }
 
+fn read_points<T>(
+    tokens: &mut PeekableTokens<T>,
+    dim: Dimension,
+    without_commas: bool,
+) -> Result<Vec<Point<T>>, WktError>
+where
+    T: WktNum + FromStr + Default,
+{
+    let mut points = Vec::new();
+    loop {
+        // Points may be written bare, as in `MULTIPOINT (1 2, 3 4)`.
+        let point = match tokens.peek() {
+            Some(Token::Number(_)) => <Point<T> as FromTokens<T>>::from_tokens(tokens, dim)?,
+            _ => <Point<T> as FromTokens<T>>::from_tokens_with_parens(tokens, dim)?,
+        };
+        points.push(point);
+        match tokens.peek() {
+            Some(Token::Comma) => {
+                tokens.next();
+            }
+            Some(Token::Number(_)) if without_commas => (),
+            _ => break,
+        }
+    }
+    Ok(points)
+}
+
 #[cfg(test)]
 mod tests {
     use super::{MultiPoint, Point};
     use types::Coord;
+    use types::Dimension;
     use {Geometry, Wkt};
 
     #[test]
@@ -73,35 +184,95 @@ mod tests {
         let mut wkt: Wkt<f64> = Wkt::from_str("MULTIPOINT ((8 4), (4 0))").ok().unwrap();
         assert_eq!(1, wkt.items.len());
         let points = match wkt.items.pop().unwrap() {
-            Geometry::MultiPoint(MultiPoint(points)) => points,
+            Geometry::MultiPoint(MultiPoint(points, Dimension::XY)) => points,
             _ => unreachable!(),
         };
         assert_eq!(2, points.len());
     }
 
+    #[test]
+    fn geometric_median_ignores_outlier() {
+        let mut wkt: Wkt<f64> =
+            Wkt::from_str("MULTIPOINT (0 0, 1 0, 0 1, 1 1, 0.5 0.5, 100 100)").unwrap();
+        let multipoint = match wkt.items.pop().unwrap() {
+            Geometry::MultiPoint(multipoint) => multipoint,
+            _ => unreachable!(),
+        };
+        let median = multipoint.geometric_median(100).unwrap();
+        assert!((median.x - 0.5).abs() < 0.1, "{:?}", median);
+        assert!((median.y - 0.5).abs() < 0.1, "{:?}", median);
+        // The centroid is dragged far towards the outlier.
+        assert!(multipoint.geometric_median(0).unwrap().x > 15.0);
+
+        assert_eq!(
+            None,
+            MultiPoint::<f64>(vec![], Dimension::XY).geometric_median(10)
+        );
+    }
+
+    #[test]
+    fn bare_multipoint() {
+        let wkt: Wkt<f64> = Wkt::from_str("MULTIPOINT (8 4, 4 0)").unwrap();
+        assert_eq!("MULTIPOINT((8 4),(4 0))", wkt.items[0].to_string());
+    }
+
+    #[test]
+    fn write_bare_multipoint() {
+        let wkt: Wkt<f64> = Wkt::from_str("MULTIPOINT Z ((8 4 1), (4 0 2))").unwrap();
+        let multipoint = match &wkt.items[0] {
+            Geometry::MultiPoint(multipoint) => multipoint,
+            _ => unreachable!(),
+        };
+        let parenthesized = multipoint.to_string();
+        let bare = multipoint.to_wkt_bare();
+        assert_eq!("MULTIPOINT Z((8 4 1),(4 0 2))", parenthesized);
+        assert_eq!("MULTIPOINT Z(8 4 1,4 0 2)", bare);
+        assert_eq!(
+            "MULTIPOINT EMPTY",
+            MultiPoint::<f64>(vec![], Dimension::XY).to_wkt_bare()
+        );
+
+        for written in &[parenthesized, bare] {
+            let read: Wkt<f64> = Wkt::from_str(written).unwrap();
+            assert_eq!(
+                format!("{:?}", wkt.items[0]),
+                format!("{:?}", read.items[0])
+            );
+        }
+    }
+
     #[test]
     fn write_empty_multipoint() {
-        let multipoint: MultiPoint<f64> = MultiPoint(vec![]);
+        let multipoint: MultiPoint<f64> = MultiPoint(vec![], Dimension::XY);
 
         assert_eq!("MULTIPOINT EMPTY", format!("{}", multipoint));
     }
 
     #[test]
     fn write_multipoint() {
-        let multipoint = MultiPoint(vec![
-            Point(Some(Coord {
-                x: 10.1,
-                y: 20.2,
-                z: None,
-                m: None,
-            })),
-            Point(Some(Coord {
-                x: 30.3,
-                y: 40.4,
-                z: None,
-                m: None,
-            })),
-        ]);
+        let multipoint = MultiPoint(
+            vec![
+                Point(
+                    Some(Coord {
+                        x: 10.1,
+                        y: 20.2,
+                        z: None,
+                        m: None,
+                    }),
+                    Dimension::XY,
+                ),
+                Point(
+                    Some(Coord {
+                        x: 30.3,
+                        y: 40.4,
+                        z: None,
+                        m: None,
+                    }),
+                    Dimension::XY,
+                ),
+            ],
+            Dimension::XY,
+        );
 
         assert_eq!(
             "MULTIPOINT((10.1 20.2),(30.3 40.4))",
@@ -12,14 +12,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::fmt;
-use std::str::FromStr;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+use format::write_empty;
 use tokenizer::PeekableTokens;
+use types::dimension::Dimension;
 use types::polygon::Polygon;
-use {FromTokens, Geometry, WktFloat};
+use {FromTokens, Geometry, WktError, WktFloat, WktNum};
 
 #[derive(Clone, Debug, Default)]
-pub struct MultiPolygon<T: WktFloat>(pub Vec<Polygon<T>>);
+pub struct MultiPolygon<T: WktNum>(pub Vec<Polygon<T>>, pub Dimension);
 
 impl<T> MultiPolygon<T>
 where
@@ -28,54 +32,78 @@ where
     pub fn as_item(self) -> Geometry<T> {
         Geometry::MultiPolygon(self)
     }
+
+    /// Appends `polygon`. An empty collection takes the dimension of its first polygon.
+    pub fn push(&mut self, polygon: Polygon<T>) {
+        if self.0.is_empty() {
+            self.1 = polygon.1;
+        }
+        self.0.push(polygon);
+    }
 }
 
 impl<T> fmt::Display for MultiPolygon<T>
 where
-    T: WktFloat + fmt::Display,
+    T: WktNum + fmt::Display,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        if self.0.is_empty() {
-            f.write_str("MULTIPOLYGON EMPTY")
-        } else {
-            let strings = self
-                .0
-                .iter()
-                .map(|p| {
-                    p.0.iter()
-                        .map(|l| {
-                            l.0.iter()
-                                .map(|c| format!("{} {}", c.x, c.y))
-                                .collect::<Vec<String>>()
-                                .join(",")
-                        })
-                        .collect::<Vec<String>>()
-                        .join("),(")
-                })
-                .collect::<Vec<String>>()
-                .join(")),((");
+        let first = self
+            .0
+            .iter()
+            .flat_map(|p| p.0.iter())
+            .flat_map(|l| l.0.iter())
+            .next();
+        match first {
+            None if self.0.is_empty() => write_empty(f, "MULTIPOLYGON", self.1),
+            _ => {
+                let strings = self
+                    .0
+                    .iter()
+                    .map(|p| {
+                        p.0.iter()
+                            .map(|l| {
+                                l.0.iter()
+                                    .map(|c| format!("{}", c))
+                                    .collect::<Vec<String>>()
+                                    .join(",")
+                            })
+                            .collect::<Vec<String>>()
+                            .join("),(")
+                    })
+                    .collect::<Vec<String>>()
+                    .join(")),((");
 
-            write!(f, "MULTIPOLYGON((({})))", strings)
+                match first.map_or(self.1, |coord| coord.dimension()) {
+                    Dimension::XY => write!(f, "MULTIPOLYGON((({})))", strings),
+                    dim => write!(f, "MULTIPOLYGON {}((({})))", dim.tag(), strings),
+                }
+            }
         }
     }
 }
 
 impl<T> FromTokens<T> for MultiPolygon<T>
 where
-    T: WktFloat + FromStr + Default,
+    T: WktNum + FromStr + Default,
 {
-    fn from_tokens(tokens: &mut PeekableTokens<T>) -> Result<Self, &'static str> {
+    fn new_empty(dim: Dimension) -> Self {
+        MultiPolygon(vec![], dim)
+    }
+
+    fn from_tokens(tokens: &mut PeekableTokens<T>, dim: Dimension) -> Result<Self, WktError> {
         let result = FromTokens::comma_many(
             <Polygon<T> as FromTokens<T>>::from_tokens_with_parens,
             tokens,
+            dim,
         );
-        result.map(MultiPolygon)
+        result.map(|x| MultiPolygon(x, dim))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::{MultiPolygon, Polygon};
+    use types::Dimension;
     use types::{Coord, LineString};
     use {Geometry, Wkt};
 
@@ -86,7 +114,7 @@ mod tests {
             .unwrap();
         assert_eq!(1, wkt.items.len());
         let polygons = match wkt.items.pop().unwrap() {
-            Geometry::MultiPolygon(MultiPolygon(polygons)) => polygons,
+            Geometry::MultiPolygon(MultiPolygon(polygons, Dimension::XY)) => polygons,
             _ => unreachable!(),
         };
         assert_eq!(2, polygons.len());
@@ -94,95 +122,113 @@ mod tests {
 
     #[test]
     fn write_empty_multipolygon() {
-        let multipolygon: MultiPolygon<f64> = MultiPolygon(vec![]);
+        let multipolygon: MultiPolygon<f64> = MultiPolygon(vec![], Dimension::XY);
 
         assert_eq!("MULTIPOLYGON EMPTY", format!("{}", multipolygon));
     }
 
     #[test]
     fn write_multipolygon() {
-        let multipolygon = MultiPolygon(vec![
-            Polygon(vec![
-                LineString(vec![
-                    Coord {
-                        x: 0.,
-                        y: 0.,
-                        z: None,
-                        m: None,
-                    },
-                    Coord {
-                        x: 20.,
-                        y: 40.,
-                        z: None,
-                        m: None,
-                    },
-                    Coord {
-                        x: 40.,
-                        y: 0.,
-                        z: None,
-                        m: None,
-                    },
-                    Coord {
-                        x: 0.,
-                        y: 0.,
-                        z: None,
-                        m: None,
-                    },
-                ]),
-                LineString(vec![
-                    Coord {
-                        x: 5.,
-                        y: 5.,
-                        z: None,
-                        m: None,
-                    },
-                    Coord {
-                        x: 20.,
-                        y: 30.,
-                        z: None,
-                        m: None,
-                    },
-                    Coord {
-                        x: 30.,
-                        y: 5.,
-                        z: None,
-                        m: None,
-                    },
-                    Coord {
-                        x: 5.,
-                        y: 5.,
-                        z: None,
-                        m: None,
-                    },
-                ]),
-            ]),
-            Polygon(vec![LineString(vec![
-                Coord {
-                    x: 40.,
-                    y: 40.,
-                    z: None,
-                    m: None,
-                },
-                Coord {
-                    x: 20.,
-                    y: 45.,
-                    z: None,
-                    m: None,
-                },
-                Coord {
-                    x: 45.,
-                    y: 30.,
-                    z: None,
-                    m: None,
-                },
-                Coord {
-                    x: 40.,
-                    y: 40.,
-                    z: None,
-                    m: None,
-                },
-            ])]),
-        ]);
+        let multipolygon = MultiPolygon(
+            vec![
+                Polygon(
+                    vec![
+                        LineString(
+                            vec![
+                                Coord {
+                                    x: 0.,
+                                    y: 0.,
+                                    z: None,
+                                    m: None,
+                                },
+                                Coord {
+                                    x: 20.,
+                                    y: 40.,
+                                    z: None,
+                                    m: None,
+                                },
+                                Coord {
+                                    x: 40.,
+                                    y: 0.,
+                                    z: None,
+                                    m: None,
+                                },
+                                Coord {
+                                    x: 0.,
+                                    y: 0.,
+                                    z: None,
+                                    m: None,
+                                },
+                            ],
+                            Dimension::XY,
+                        ),
+                        LineString(
+                            vec![
+                                Coord {
+                                    x: 5.,
+                                    y: 5.,
+                                    z: None,
+                                    m: None,
+                                },
+                                Coord {
+                                    x: 20.,
+                                    y: 30.,
+                                    z: None,
+                                    m: None,
+                                },
+                                Coord {
+                                    x: 30.,
+                                    y: 5.,
+                                    z: None,
+                                    m: None,
+                                },
+                                Coord {
+                                    x: 5.,
+                                    y: 5.,
+                                    z: None,
+                                    m: None,
+                                },
+                            ],
+                            Dimension::XY,
+                        ),
+                    ],
+                    Dimension::XY,
+                ),
+                Polygon(
+                    vec![LineString(
+                        vec![
+                            Coord {
+                                x: 40.,
+                                y: 40.,
+                                z: None,
+                                m: None,
+                            },
+                            Coord {
+                                x: 20.,
+                                y: 45.,
+                                z: None,
+                                m: None,
+                            },
+                            Coord {
+                                x: 45.,
+                                y: 30.,
+                                z: None,
+                                m: None,
+                            },
+                            Coord {
+                                x: 40.,
+                                y: 40.,
+                                z: None,
+                                m: None,
+                            },
+                        ],
+                        Dimension::XY,
+                    )],
+                    Dimension::XY,
+                ),
+            ],
+            Dimension::XY,
+        );
 
         assert_eq!(
             "MULTIPOLYGON(((0 0,20 40,40 0,0 0),(5 5,20 30,30 5,5 5)),((40 40,20 45,45 30,40 40)))",
@@ -12,14 +12,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::fmt;
-use std::str::FromStr;
+use alloc::string::{String, ToString};
+use core::fmt;
+use core::str::FromStr;
+use format::write_empty;
 use tokenizer::PeekableTokens;
 use types::coord::Coord;
-use {FromTokens, Geometry, WktFloat};
+use types::dimension::Dimension;
+use {FromTokens, Geometry, WktError, WktFloat, WktNum};
 
 #[derive(Clone, Debug, Default)]
-pub struct Point<T: WktFloat>(pub Option<Coord<T>>);
+pub struct Point<T: WktNum>(pub Option<Coord<T>>, pub Dimension);
 
 impl<T> Point<T>
 where
@@ -28,11 +31,62 @@ where
     pub fn as_item(self) -> Geometry<T> {
         Geometry::Point(self)
     }
+
+    /// Encodes the point, taking x as the longitude and y as the latitude, as a geohash of
+    /// `precision` characters for spatial bucketing. Empty points, coordinates outside of
+    /// the longitude and latitude ranges and a zero precision fail with
+    /// [`WktError::InvalidGeohash`].
+    pub fn geohash(&self, precision: usize) -> Result<String, WktError> {
+        const BASE32: &[u8] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+
+        let coord = self
+            .0
+            .as_ref()
+            .ok_or(WktError::InvalidGeohash("empty point"))?;
+        let (lon, lat) = match (coord.x.to_f64(), coord.y.to_f64()) {
+            (Some(lon), Some(lat))
+                if (-180.0..=180.0).contains(&lon) && (-90.0..=90.0).contains(&lat) =>
+            {
+                (lon, lat)
+            }
+            _ => return Err(WktError::InvalidGeohash("coordinate out of range")),
+        };
+        if precision == 0 {
+            return Err(WktError::InvalidGeohash("zero precision"));
+        }
+
+        // Bits alternate between halving the longitude and the latitude range, starting
+        // with the longitude, five to a character.
+        let mut lon_range = (-180.0, 180.0);
+        let mut lat_range = (-90.0, 90.0);
+        let mut hash = String::with_capacity(precision);
+        let mut even = true;
+        for _ in 0..precision {
+            let mut index = 0;
+            for _ in 0..5 {
+                let (value, range) = match even {
+                    true => (lon, &mut lon_range),
+                    false => (lat, &mut lat_range),
+                };
+                let mid = (range.0 + range.1) / 2.0;
+                index <<= 1;
+                if value >= mid {
+                    index |= 1;
+                    range.0 = mid;
+                } else {
+                    range.1 = mid;
+                }
+                even = !even;
+            }
+            hash.push(BASE32[index] as char);
+        }
+        Ok(hash)
+    }
 }
 
 impl<T> fmt::Display for Point<T>
 where
-    T: WktFloat + fmt::Display,
+    T: WktNum + fmt::Display,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         match self.0 {
@@ -50,32 +104,70 @@ where
 
                 write!(f, "POINT{}({})", lrs, coord)
             }
-            None => f.write_str("POINT EMPTY"),
+            None => write_empty(f, "POINT", self.1),
         }
     }
 }
 
 impl<T> FromTokens<T> for Point<T>
 where
-    T: WktFloat + FromStr + Default,
+    T: WktNum + FromStr + Default,
 {
-    fn from_tokens(tokens: &mut PeekableTokens<T>) -> Result<Self, &'static str> {
-        let result = <Coord<T> as FromTokens<T>>::from_tokens(tokens);
-        result.map(|coord| Point(Some(coord)))
+    fn new_empty(dim: Dimension) -> Self {
+        Point(None, dim)
+    }
+
+    fn from_tokens(tokens: &mut PeekableTokens<T>, dim: Dimension) -> Result<Self, WktError> {
+        let result = <Coord<T> as FromTokens<T>>::from_tokens(tokens, dim);
+        result.map(|coord| Point(Some(coord), dim))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::{Coord, Point};
-    use {Geometry, Wkt};
+    use types::Dimension;
+    use {Geometry, Wkt, WktError};
+
+    #[test]
+    fn geohash() {
+        let point = Point(
+            Some(Coord {
+                x: 10.40744,
+                y: 57.64911,
+                z: None,
+                m: None,
+            }),
+            Dimension::XY,
+        );
+        assert_eq!("u4pruydqqvj", point.geohash(11).unwrap());
+        assert_eq!("u4pr", point.geohash(4).unwrap());
+
+        let point = Point(
+            Some(Coord {
+                x: -74.0,
+                y: 91.0,
+                z: None,
+                m: None,
+            }),
+            Dimension::XY,
+        );
+        assert_eq!(
+            WktError::InvalidGeohash("coordinate out of range"),
+            point.geohash(5).unwrap_err()
+        );
+        assert_eq!(
+            WktError::InvalidGeohash("empty point"),
+            Point::<f64>(None, Dimension::XY).geohash(5).unwrap_err()
+        );
+    }
 
     #[test]
     fn basic_point() {
-        let mut wkt = Wkt::from_str("POINT (10 -20)").ok().unwrap();
+        let mut wkt: Wkt<f64> = Wkt::from_str("POINT (10 -20)").ok().unwrap();
         assert_eq!(1, wkt.items.len());
         let coord = match wkt.items.pop().unwrap() {
-            Geometry::Point(Point(Some(coord))) => coord,
+            Geometry::Point(Point(Some(coord), Dimension::XY)) => coord,
             _ => unreachable!(),
         };
         assert_eq!(10.0, coord.x);
@@ -92,7 +184,7 @@ mod tests {
                 .unwrap();
         assert_eq!(1, wkt.items.len());
         let coord = match wkt.items.pop().unwrap() {
-            Geometry::Point(Point(Some(coord))) => coord,
+            Geometry::Point(Point(Some(coord), Dimension::XY)) => coord,
             _ => unreachable!(),
         };
         assert_eq!(10.0, coord.x);
@@ -101,6 +193,22 @@ mod tests {
         assert_eq!(None, coord.m);
     }
 
+    #[test]
+    fn point_with_dimension_tag() {
+        let mut wkt: Wkt<f64> = Wkt::from_str("POINT ZM (10 -20 30 40)").ok().unwrap();
+        let coord = match wkt.items.pop().unwrap() {
+            Geometry::Point(Point(Some(coord), Dimension::XYZM)) => coord,
+            _ => unreachable!(),
+        };
+        assert_eq!(Some(30.0), coord.z);
+        assert_eq!(Some(40.0), coord.m);
+
+        <Wkt<f64>>::from_str("POINT Z (10 -20)").err().unwrap();
+        <Wkt<f64>>::from_str("POINT M (10 -20 30 40)")
+            .err()
+            .unwrap();
+    }
+
     #[test]
     fn invalid_points() {
         <Wkt<f64>>::from_str("POINT ()").err().unwrap();
@@ -111,55 +219,67 @@ mod tests {
 
     #[test]
     fn write_empty_point() {
-        let point: Point<f64> = Point(None);
+        let point: Point<f64> = Point(None, Dimension::XY);
 
         assert_eq!("POINT EMPTY", format!("{}", point));
     }
 
     #[test]
     fn write_2d_point() {
-        let point = Point(Some(Coord {
-            x: 10.12345,
-            y: 20.67891,
-            z: None,
-            m: None,
-        }));
+        let point = Point(
+            Some(Coord {
+                x: 10.12345,
+                y: 20.67891,
+                z: None,
+                m: None,
+            }),
+            Dimension::XY,
+        );
 
         assert_eq!("POINT(10.12345 20.67891)", format!("{}", point));
     }
 
     #[test]
     fn write_point_with_z_coord() {
-        let point = Point(Some(Coord {
-            x: 10.12345,
-            y: 20.67891,
-            z: Some(-32.56455),
-            m: None,
-        }));
+        let point = Point(
+            Some(Coord {
+                x: 10.12345,
+                y: 20.67891,
+                z: Some(-32.56455),
+                m: None,
+            }),
+            Dimension::XY,
+        );
 
         assert_eq!("POINT Z(10.12345 20.67891 -32.56455)", format!("{}", point));
     }
 
     #[test]
     fn write_point_with_m_coord() {
-        let point = Point(Some(Coord {
-            x: 10.12345,
-            y: 20.67891,
-            z: None,
-            m: Some(10.),
-        }));
+        let point = Point(
+            Some(Coord {
+                x: 10.12345,
+                y: 20.67891,
+                z: None,
+                m: Some(10.),
+            }),
+            Dimension::XY,
+        );
 
         assert_eq!("POINT M(10.12345 20.67891 10)", format!("{}", point));
     }
 
     #[test]
     fn write_point_with_zm_coord() {
-        let point = Point(Some(Coord {
-            x: 10.12345,
-            y: 20.67891,
-            z: Some(-32.56455),
-            m: Some(10.),
-        }));
+        let point = Point(
+            Some(Coord {
+                x: 10.12345,
+                y: 20.67891,
+                z: Some(-32.56455),
+                m: Some(10.),
+            }),
+            Dimension::XY,
+        );
 
         assert_eq!(
             "POINT ZM(10.12345 20.67891 -32.56455 10)",
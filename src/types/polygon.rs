@@ -12,67 +12,225 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::fmt;
-use std::str::FromStr;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+use format::write_empty;
 use tokenizer::PeekableTokens;
+use types::coord::Coord;
+use types::dimension::Dimension;
 use types::linestring::LineString;
-use {FromTokens, Geometry, WktFloat};
+use {FromTokens, Geometry, WktError, WktFloat, WktNum};
+
+/// The orientation of the exterior ring of a polygon, see [`Polygon::orient`]. Interior
+/// rings take the opposite one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Winding {
+    /// A counter-clockwise exterior, as in OGC simple features and GeoJSON
+    CounterClockwise,
+    /// A clockwise exterior, as in ESRI shapefiles
+    Clockwise,
+}
 
 #[derive(Clone, Debug, Default)]
-pub struct Polygon<T: WktFloat>(pub Vec<LineString<T>>);
+pub struct Polygon<T: WktNum>(pub Vec<LineString<T>>, pub Dimension);
 
 impl<T> Polygon<T>
 where
     T: WktFloat,
 {
+    /// Builds a polygon from an exterior ring and any number of interior rings. Rings
+    /// which are not closed are closed by repeating their first coordinate, and the
+    /// result is checked with [`Polygon::validate`].
+    pub fn new(exterior: Vec<Coord<T>>, interiors: Vec<Vec<Coord<T>>>) -> Result<Self, WktError> {
+        let dim = exterior.first().map_or(Dimension::XY, Coord::dimension);
+        let polygon = Polygon(
+            core::iter::once(exterior)
+                .chain(interiors)
+                .map(|mut coords| {
+                    match (coords.first(), coords.last()) {
+                        (Some(first), Some(last)) if first != last => {
+                            let first = first.clone();
+                            coords.push(first);
+                        }
+                        _ => (),
+                    }
+                    LineString(coords, dim)
+                })
+                .collect(),
+            dim,
+        );
+        polygon.validate()?;
+        Ok(polygon)
+    }
+
+    /// The two dimensional area and area centroid, computed together in one shoelace pass
+    /// over the rings. Interior rings are subtracted whatever their orientation. Returns
+    /// `None` if the area is zero.
+    pub fn area_and_centroid(&self) -> Option<(T, Coord<T>)> {
+        let (mut area, mut x, mut y) = (T::zero(), T::zero(), T::zero());
+        for (i, ring) in self.0.iter().enumerate() {
+            let (mut ring_area, mut ring_x, mut ring_y) = (T::zero(), T::zero(), T::zero());
+            for segment in ring.0.windows(2) {
+                let (a, b) = (&segment[0], &segment[1]);
+                let cross = a.x * b.y - b.x * a.y;
+                ring_area = ring_area + cross;
+                ring_x = ring_x + (a.x + b.x) * cross;
+                ring_y = ring_y + (a.y + b.y) * cross;
+            }
+            // Orient the exterior ring positively and the interior rings negatively.
+            let sign = if (ring_area < T::zero()) == (i == 0) {
+                -T::one()
+            } else {
+                T::one()
+            };
+            area = area + sign * ring_area;
+            x = x + sign * ring_x;
+            y = y + sign * ring_y;
+        }
+        if area.is_zero() {
+            return None;
+        }
+        let three = T::from(3)?;
+        let centroid = Coord {
+            x: x / (three * area),
+            y: y / (three * area),
+            z: None,
+            m: None,
+        };
+        Some((area / T::from(2)?, centroid))
+    }
+
     pub fn as_item(self) -> Geometry<T> {
         Geometry::Polygon(self)
     }
+
+    /// Checks that every ring is a valid OGC linear ring: closed, with at least four
+    /// coordinates. Parsing does not enforce this, so call it when strictness matters.
+    pub fn validate(&self) -> Result<(), WktError> {
+        for ring in &self.0 {
+            if ring.0.len() < 4 {
+                return Err(WktError::TooFewCoordinates {
+                    geometry: "LinearRing",
+                    min: 4,
+                    found: ring.0.len(),
+                });
+            }
+            if !ring.is_closed() {
+                return Err(WktError::UnclosedRing);
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether the exterior ring winds counter-clockwise, from the sign of its shoelace
+    /// area. An empty or zero area exterior is not counter-clockwise.
+    pub fn is_ccw_exterior(&self) -> bool {
+        self.0
+            .first()
+            .is_some_and(|exterior| signed_area(&exterior.0) > T::zero())
+    }
+
+    /// Reverses the rings which do not follow `convention`, for consumers with strict
+    /// winding requirements. Zero area rings are left as they are.
+    pub fn orient(&mut self, convention: Winding) {
+        for (i, ring) in self.0.iter_mut().enumerate() {
+            let ccw = (i == 0) == (convention == Winding::CounterClockwise);
+            let area = signed_area(&ring.0);
+            if !area.is_zero() && (area > T::zero()) != ccw {
+                ring.0.reverse();
+            }
+        }
+    }
+}
+
+/// Twice the signed area of `ring`, positive when it winds counter-clockwise. The ring
+/// is closed implicitly if needed.
+fn signed_area<T: WktFloat>(ring: &[Coord<T>]) -> T {
+    ring.iter()
+        .zip(ring.iter().cycle().skip(1))
+        .fold(T::zero(), |area, (a, b)| area + a.x * b.y - b.x * a.y)
 }
 
 impl<T> fmt::Display for Polygon<T>
 where
-    T: WktFloat + fmt::Display,
+    T: WktNum + fmt::Display,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        if self.0.is_empty() {
-            f.write_str("POLYGON EMPTY")
-        } else {
-            let strings = self
-                .0
-                .iter()
-                .map(|l| {
-                    l.0.iter()
-                        .map(|c| format!("{} {}", c.x, c.y))
-                        .collect::<Vec<_>>()
-                        .join(",")
-                })
-                .collect::<Vec<_>>()
-                .join("),(");
+        match self.0.iter().flat_map(|l| l.0.iter()).next() {
+            None => write_empty(f, "POLYGON", self.1),
+            Some(coord) => {
+                let strings = self
+                    .0
+                    .iter()
+                    .map(|l| {
+                        l.0.iter()
+                            .map(|c| format!("{}", c))
+                            .collect::<Vec<_>>()
+                            .join(",")
+                    })
+                    .collect::<Vec<_>>()
+                    .join("),(");
 
-            write!(f, "POLYGON(({}))", strings)
+                match coord.dimension() {
+                    Dimension::XY => write!(f, "POLYGON(({}))", strings),
+                    dim => write!(f, "POLYGON {}(({}))", dim.tag(), strings),
+                }
+            }
         }
     }
 }
 
 impl<T> FromTokens<T> for Polygon<T>
 where
-    T: WktFloat + FromStr + Default,
+    T: WktNum + FromStr + Default,
 {
-    fn from_tokens(tokens: &mut PeekableTokens<T>) -> Result<Self, &'static str> {
+    fn new_empty(dim: Dimension) -> Self {
+        Polygon(vec![], dim)
+    }
+
+    fn from_tokens(tokens: &mut PeekableTokens<T>, dim: Dimension) -> Result<Self, WktError> {
         let result = FromTokens::comma_many(
             <LineString<T> as FromTokens<T>>::from_tokens_with_parens,
             tokens,
+            dim,
         );
-        result.map(Polygon)
+        result.map(|x| Polygon(x, dim))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{LineString, Polygon};
+    use super::{LineString, Polygon, Winding};
     use types::Coord;
-    use {Geometry, Wkt};
+    use types::Dimension;
+    use {Geometry, Wkt, WktError};
+
+    #[test]
+    fn area_and_centroid() {
+        let square = parse_polygon("POLYGON ((0 0, 1 0, 1 1, 0 1, 0 0))");
+        let centroid = Coord {
+            x: 0.5,
+            y: 0.5,
+            z: None,
+            m: None,
+        };
+        assert_eq!(Some((1.0, centroid.clone())), square.area_and_centroid());
+        let clockwise = parse_polygon("POLYGON ((0 0, 0 1, 1 1, 1 0, 0 0))");
+        assert_eq!(Some((1.0, centroid)), clockwise.area_and_centroid());
+
+        let holed = parse_polygon("POLYGON ((0 0, 4 0, 4 4, 0 4, 0 0), (0 0, 0 2, 2 2, 2 0, 0 0))");
+        let (area, centroid) = holed.area_and_centroid().unwrap();
+        assert_eq!(12.0, area);
+        assert!((centroid.x - 7. / 3.).abs() < 1e-12, "{:?}", centroid);
+        assert!((centroid.y - 7. / 3.).abs() < 1e-12, "{:?}", centroid);
+
+        assert_eq!(
+            None,
+            parse_polygon("POLYGON ((0 0, 1 1, 2 2, 0 0))").area_and_centroid()
+        );
+        assert_eq!(None, parse_polygon("POLYGON EMPTY").area_and_centroid());
+    }
 
     #[test]
     fn basic_polygon() {
@@ -82,75 +240,201 @@ mod tests {
                 .unwrap();
         assert_eq!(1, wkt.items.len());
         let lines = match wkt.items.pop().unwrap() {
-            Geometry::Polygon(Polygon(lines)) => lines,
+            Geometry::Polygon(Polygon(lines, Dimension::XY)) => lines,
             _ => unreachable!(),
         };
         assert_eq!(2, lines.len());
     }
 
+    fn parse_polygon(s: &str) -> Polygon<f64> {
+        match Wkt::from_str(s).unwrap().items.pop().unwrap() {
+            Geometry::Polygon(polygon) => polygon,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn validate_closed_polygon() {
+        let polygon =
+            parse_polygon("POLYGON ((0 0, 1 0, 1 1, 0 0), (0.5 0.5, 0.6 0.5, 0.6 0.6, 0.5 0.5))");
+        assert_eq!(Ok(()), polygon.validate());
+    }
+
+    #[test]
+    fn validate_unclosed_polygon() {
+        let polygon = parse_polygon("POLYGON ((0 0, 1 0, 1 1, 0 1))");
+        assert_eq!(Err(WktError::UnclosedRing), polygon.validate());
+    }
+
+    #[test]
+    fn validate_too_short_polygon() {
+        let polygon = parse_polygon("POLYGON ((0 0, 1 0, 1 1))");
+        assert_eq!(
+            Err(WktError::TooFewCoordinates {
+                geometry: "LinearRing",
+                min: 4,
+                found: 3
+            }),
+            polygon.validate()
+        );
+    }
+
+    #[test]
+    fn new_polygon_with_hole() {
+        let coord = |x, y| Coord {
+            x,
+            y,
+            z: None,
+            m: None,
+        };
+        let polygon = Polygon::new(
+            vec![
+                coord(0., 0.),
+                coord(10., 0.),
+                coord(10., 10.),
+                coord(0., 10.),
+            ],
+            vec![vec![
+                coord(2., 2.),
+                coord(4., 2.),
+                coord(4., 4.),
+                coord(2., 2.),
+            ]],
+        )
+        .unwrap();
+
+        assert_eq!(
+            "POLYGON((0 0,10 0,10 10,0 10,0 0),(2 2,4 2,4 4,2 2))",
+            format!("{}", polygon)
+        );
+    }
+
+    #[test]
+    fn winding_order() {
+        const CCW: &str = "POLYGON ((0 0, 10 0, 10 10, 0 10, 0 0), (2 2, 2 4, 4 4, 4 2, 2 2))";
+        const CW: &str = "POLYGON ((0 0, 0 10, 10 10, 10 0, 0 0), (2 2, 4 2, 4 4, 2 4, 2 2))";
+
+        let mut polygon = parse_polygon(CCW);
+        assert!(polygon.is_ccw_exterior());
+        polygon.orient(Winding::CounterClockwise);
+        assert_eq!(parse_polygon(CCW).to_string(), polygon.to_string());
+        polygon.orient(Winding::Clockwise);
+        assert!(!polygon.is_ccw_exterior());
+        assert_eq!(
+            "POLYGON((0 0,0 10,10 10,10 0,0 0),(2 2,4 2,4 4,2 4,2 2))",
+            polygon.to_string()
+        );
+
+        let mut polygon = parse_polygon(CW);
+        assert!(!polygon.is_ccw_exterior());
+        polygon.orient(Winding::CounterClockwise);
+        assert!(polygon.is_ccw_exterior());
+        assert_eq!(
+            "POLYGON((0 0,10 0,10 10,0 10,0 0),(2 2,2 4,4 4,4 2,2 2))",
+            polygon.to_string()
+        );
+
+        // Holes are oriented against the shell even when written like it.
+        let mut polygon = parse_polygon("POLYGON ((0 0, 10 0, 10 10, 0 0), (2 1, 4 1, 4 3, 2 1))");
+        polygon.orient(Winding::CounterClockwise);
+        assert_eq!(
+            "POLYGON((0 0,10 0,10 10,0 0),(2 1,4 3,4 1,2 1))",
+            polygon.to_string()
+        );
+
+        assert!(!parse_polygon("POLYGON EMPTY").is_ccw_exterior());
+    }
+
+    #[test]
+    fn new_polygon_too_short() {
+        let coord = |x, y| Coord {
+            x,
+            y,
+            z: None,
+            m: None,
+        };
+        assert_eq!(
+            WktError::TooFewCoordinates {
+                geometry: "LinearRing",
+                min: 4,
+                found: 3
+            },
+            Polygon::new(vec![coord(0., 0.), coord(1., 0.)], vec![]).unwrap_err()
+        );
+    }
+
     #[test]
     fn write_empty_polygon() {
-        let polygon: Polygon<f64> = Polygon(vec![]);
+        let polygon: Polygon<f64> = Polygon(vec![], Dimension::XY);
 
         assert_eq!("POLYGON EMPTY", format!("{}", polygon));
     }
 
     #[test]
     fn write_polygon() {
-        let polygon = Polygon(vec![
-            LineString(vec![
-                Coord {
-                    x: 0.,
-                    y: 0.,
-                    z: None,
-                    m: None,
-                },
-                Coord {
-                    x: 20.,
-                    y: 40.,
-                    z: None,
-                    m: None,
-                },
-                Coord {
-                    x: 40.,
-                    y: 0.,
-                    z: None,
-                    m: None,
-                },
-                Coord {
-                    x: 0.,
-                    y: 0.,
-                    z: None,
-                    m: None,
-                },
-            ]),
-            LineString(vec![
-                Coord {
-                    x: 5.,
-                    y: 5.,
-                    z: None,
-                    m: None,
-                },
-                Coord {
-                    x: 20.,
-                    y: 30.,
-                    z: None,
-                    m: None,
-                },
-                Coord {
-                    x: 30.,
-                    y: 5.,
-                    z: None,
-                    m: None,
-                },
-                Coord {
-                    x: 5.,
-                    y: 5.,
-                    z: None,
-                    m: None,
-                },
-            ]),
-        ]);
+        let polygon = Polygon(
+            vec![
+                LineString(
+                    vec![
+                        Coord {
+                            x: 0.,
+                            y: 0.,
+                            z: None,
+                            m: None,
+                        },
+                        Coord {
+                            x: 20.,
+                            y: 40.,
+                            z: None,
+                            m: None,
+                        },
+                        Coord {
+                            x: 40.,
+                            y: 0.,
+                            z: None,
+                            m: None,
+                        },
+                        Coord {
+                            x: 0.,
+                            y: 0.,
+                            z: None,
+                            m: None,
+                        },
+                    ],
+                    Dimension::XY,
+                ),
+                LineString(
+                    vec![
+                        Coord {
+                            x: 5.,
+                            y: 5.,
+                            z: None,
+                            m: None,
+                        },
+                        Coord {
+                            x: 20.,
+                            y: 30.,
+                            z: None,
+                            m: None,
+                        },
+                        Coord {
+                            x: 30.,
+                            y: 5.,
+                            z: None,
+                            m: None,
+                        },
+                        Coord {
+                            x: 5.,
+                            y: 5.,
+                            z: None,
+                            m: None,
+                        },
+                    ],
+                    Dimension::XY,
+                ),
+            ],
+            Dimension::XY,
+        );
 
         assert_eq!(
             "POLYGON((0 0,20 40,40 0,0 0),(5 5,20 30,30 5,5 5))",
@@ -0,0 +1,150 @@
+// Copyright 2014-2021 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+use format::write_empty;
+use tokenizer::PeekableTokens;
+use types::dimension::Dimension;
+use types::polygon::Polygon;
+use {FromTokens, Geometry, WktError, WktFloat, WktNum};
+
+/// A surface made of polygon patches sharing edges, such as the faces of a solid.
+#[derive(Clone, Debug, Default)]
+pub struct PolyhedralSurface<T: WktNum>(pub Vec<Polygon<T>>, pub Dimension);
+
+impl<T> PolyhedralSurface<T>
+where
+    T: WktFloat,
+{
+    pub fn as_item(self) -> Geometry<T> {
+        Geometry::PolyhedralSurface(self)
+    }
+}
+
+impl<T> fmt::Display for PolyhedralSurface<T>
+where
+    T: WktNum + fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        let first = self
+            .0
+            .iter()
+            .flat_map(|p| p.0.iter())
+            .flat_map(|l| l.0.iter())
+            .next();
+        match first {
+            None => write_empty(f, "POLYHEDRALSURFACE", self.1),
+            Some(coord) => {
+                let strings = self
+                    .0
+                    .iter()
+                    .map(|p| {
+                        p.0.iter()
+                            .map(|l| {
+                                l.0.iter()
+                                    .map(|c| format!("{}", c))
+                                    .collect::<Vec<_>>()
+                                    .join(",")
+                            })
+                            .collect::<Vec<_>>()
+                            .join("),(")
+                    })
+                    .collect::<Vec<_>>()
+                    .join(")),((");
+
+                match coord.dimension() {
+                    Dimension::XY => write!(f, "POLYHEDRALSURFACE((({})))", strings),
+                    dim => write!(f, "POLYHEDRALSURFACE {}((({})))", dim.tag(), strings),
+                }
+            }
+        }
+    }
+}
+
+impl<T> FromTokens<T> for PolyhedralSurface<T>
+where
+    T: WktNum + FromStr + Default,
+{
+    fn new_empty(dim: Dimension) -> Self {
+        PolyhedralSurface(vec![], dim)
+    }
+
+    fn from_tokens(tokens: &mut PeekableTokens<T>, dim: Dimension) -> Result<Self, WktError> {
+        let result = FromTokens::comma_many(
+            <Polygon<T> as FromTokens<T>>::from_tokens_with_parens,
+            tokens,
+            dim,
+        );
+        result.map(|x| PolyhedralSurface(x, dim))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PolyhedralSurface;
+    use types::Dimension;
+    use {Geometry, Wkt};
+
+    const CUBE: &str = "POLYHEDRALSURFACE Z (\
+        ((0 0 0, 0 1 0, 1 1 0, 1 0 0, 0 0 0)),\
+        ((0 0 0, 0 1 0, 0 1 1, 0 0 1, 0 0 0)),\
+        ((0 0 0, 1 0 0, 1 0 1, 0 0 1, 0 0 0)),\
+        ((1 1 1, 1 0 1, 0 0 1, 0 1 1, 1 1 1)),\
+        ((1 1 1, 1 0 1, 1 0 0, 1 1 0, 1 1 1)),\
+        ((1 1 1, 1 1 0, 0 1 0, 0 1 1, 1 1 1)))";
+
+    #[test]
+    fn basic_polyhedralsurface() {
+        let mut wkt: Wkt<f64> = Wkt::from_str(CUBE).ok().unwrap();
+        assert_eq!(1, wkt.items.len());
+        let faces = match wkt.items.pop().unwrap() {
+            Geometry::PolyhedralSurface(PolyhedralSurface(faces, Dimension::XYZ)) => faces,
+            _ => unreachable!(),
+        };
+        assert_eq!(6, faces.len());
+        for face in &faces {
+            assert_eq!(1, face.0.len());
+            assert_eq!(5, face.0[0].0.len());
+            assert!(face.0[0].0.iter().all(|c| c.z.is_some() && c.m.is_none()));
+        }
+        assert_eq!(Some(1.0), faces[3].0[0].0[0].z);
+    }
+
+    #[test]
+    fn polyhedralsurface_missing_z() {
+        <Wkt<f64>>::from_str("POLYHEDRALSURFACE Z (((0 0, 0 1, 1 1, 0 0)))")
+            .err()
+            .unwrap();
+    }
+
+    #[test]
+    fn write_empty_polyhedralsurface() {
+        let surface: PolyhedralSurface<f64> = PolyhedralSurface(vec![], Dimension::XY);
+
+        assert_eq!("POLYHEDRALSURFACE EMPTY", format!("{}", surface));
+    }
+
+    #[test]
+    fn write_polyhedralsurface() {
+        let wkt: Wkt<f64> =
+            Wkt::from_str("POLYHEDRALSURFACE Z (((0 0 0, 0 1 0, 1 1 0, 0 0 0)))").unwrap();
+
+        assert_eq!(
+            "POLYHEDRALSURFACE Z(((0 0 0,0 1 0,1 1 0,0 0 0)))",
+            format!("{}", wkt.items[0])
+        );
+    }
+}
@@ -0,0 +1,135 @@
+// Copyright 2014-2021 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+use format::write_empty;
+use tokenizer::PeekableTokens;
+use types::dimension::Dimension;
+use types::triangle::Triangle;
+use {FromTokens, Geometry, WktError, WktFloat, WktNum};
+
+/// A triangulated irregular network: a polyhedral surface made only of triangles.
+#[derive(Clone, Debug, Default)]
+pub struct Tin<T: WktNum>(pub Vec<Triangle<T>>, pub Dimension);
+
+impl<T> Tin<T>
+where
+    T: WktFloat,
+{
+    pub fn as_item(self) -> Geometry<T> {
+        Geometry::Tin(self)
+    }
+}
+
+impl<T> fmt::Display for Tin<T>
+where
+    T: WktNum + fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        let first = self
+            .0
+            .iter()
+            .flat_map(|t| t.0.iter())
+            .flat_map(|l| l.0.iter())
+            .next();
+        match first {
+            None => write_empty(f, "TIN", self.1),
+            Some(coord) => {
+                let strings = self
+                    .0
+                    .iter()
+                    .map(|t| {
+                        t.0.iter()
+                            .map(|l| {
+                                l.0.iter()
+                                    .map(|c| format!("{}", c))
+                                    .collect::<Vec<_>>()
+                                    .join(",")
+                            })
+                            .collect::<Vec<_>>()
+                            .join("),(")
+                    })
+                    .collect::<Vec<_>>()
+                    .join(")),((");
+
+                match coord.dimension() {
+                    Dimension::XY => write!(f, "TIN((({})))", strings),
+                    dim => write!(f, "TIN {}((({})))", dim.tag(), strings),
+                }
+            }
+        }
+    }
+}
+
+impl<T> FromTokens<T> for Tin<T>
+where
+    T: WktNum + FromStr + Default,
+{
+    fn new_empty(dim: Dimension) -> Self {
+        Tin(vec![], dim)
+    }
+
+    fn from_tokens(tokens: &mut PeekableTokens<T>, dim: Dimension) -> Result<Self, WktError> {
+        let result = FromTokens::comma_many(
+            <Triangle<T> as FromTokens<T>>::from_tokens_with_parens,
+            tokens,
+            dim,
+        );
+        result.map(|x| Tin(x, dim))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Tin;
+    use types::Dimension;
+    use {Geometry, Wkt};
+
+    #[test]
+    fn basic_tin() {
+        let mut wkt: Wkt<f64> =
+            Wkt::from_str("TIN Z (((0 0 0, 0 0 1, 0 1 0, 0 0 0)), ((0 0 0, 0 1 0, 1 1 0, 0 0 0)))")
+                .ok()
+                .unwrap();
+        assert_eq!(1, wkt.items.len());
+        let triangles = match wkt.items.pop().unwrap() {
+            Geometry::Tin(Tin(triangles, Dimension::XYZ)) => triangles,
+            _ => unreachable!(),
+        };
+        assert_eq!(2, triangles.len());
+    }
+
+    #[test]
+    fn tin_with_invalid_triangle() {
+        <Wkt<f64>>::from_str("TIN (((0 0, 0 1, 1 1, 1 0, 0 0)))")
+            .err()
+            .unwrap();
+    }
+
+    #[test]
+    fn write_empty_tin() {
+        let tin: Tin<f64> = Tin(vec![], Dimension::XY);
+
+        assert_eq!("TIN EMPTY", format!("{}", tin));
+    }
+
+    #[test]
+    fn write_tin() {
+        let wkt: Wkt<f64> = Wkt::from_str("TIN (((0 0, 0 1, 1 0, 0 0)))").unwrap();
+
+        assert_eq!("TIN(((0 0,0 1,1 0,0 0)))", format!("{}", wkt.items[0]));
+    }
+}
@@ -0,0 +1,171 @@
+// Copyright 2014-2021 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+use format::write_empty;
+use tokenizer::PeekableTokens;
+use types::dimension::Dimension;
+use types::linestring::LineString;
+use types::polygon::Polygon;
+use {FromTokens, Geometry, WktError, WktFloat, WktNum};
+
+/// A polygon with a single ring of three distinct coordinates plus the closing one.
+#[derive(Clone, Debug, Default)]
+pub struct Triangle<T: WktNum>(pub Vec<LineString<T>>, pub Dimension);
+
+impl<T> Triangle<T>
+where
+    T: WktFloat,
+{
+    pub fn as_item(self) -> Geometry<T> {
+        Geometry::Triangle(self)
+    }
+}
+
+impl<T> fmt::Display for Triangle<T>
+where
+    T: WktNum + fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self.0.iter().flat_map(|l| l.0.iter()).next() {
+            None => write_empty(f, "TRIANGLE", self.1),
+            Some(coord) => {
+                let strings = self
+                    .0
+                    .iter()
+                    .map(|l| {
+                        l.0.iter()
+                            .map(|c| format!("{}", c))
+                            .collect::<Vec<_>>()
+                            .join(",")
+                    })
+                    .collect::<Vec<_>>()
+                    .join("),(");
+
+                match coord.dimension() {
+                    Dimension::XY => write!(f, "TRIANGLE(({}))", strings),
+                    dim => write!(f, "TRIANGLE {}(({}))", dim.tag(), strings),
+                }
+            }
+        }
+    }
+}
+
+impl<T> FromTokens<T> for Triangle<T>
+where
+    T: WktNum + FromStr + Default,
+{
+    fn new_empty(dim: Dimension) -> Self {
+        Triangle(vec![], dim)
+    }
+
+    fn from_tokens(tokens: &mut PeekableTokens<T>, dim: Dimension) -> Result<Self, WktError> {
+        let Polygon(rings, _) = <Polygon<T> as FromTokens<T>>::from_tokens(tokens, dim)?;
+        if rings.len() != 1 {
+            return Err(WktError::Syntax("Expected exactly one ring in TRIANGLE"));
+        }
+        if rings[0].0.len() != 4 {
+            return Err(WktError::WrongNumberOfCoordinates {
+                geometry: "Triangle",
+                expected: 4,
+                found: rings[0].0.len(),
+            });
+        }
+        Ok(Triangle(rings, dim))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LineString, Triangle};
+    use types::Coord;
+    use types::Dimension;
+    use {Geometry, Wkt, WktError};
+
+    #[test]
+    fn basic_triangle() {
+        let mut wkt: Wkt<f64> = Wkt::from_str("TRIANGLE ((0 0, 0 9, 9 0, 0 0))")
+            .ok()
+            .unwrap();
+        assert_eq!(1, wkt.items.len());
+        let rings = match wkt.items.pop().unwrap() {
+            Geometry::Triangle(Triangle(rings, Dimension::XY)) => rings,
+            _ => unreachable!(),
+        };
+        assert_eq!(1, rings.len());
+        assert_eq!(4, rings[0].0.len());
+    }
+
+    #[test]
+    fn invalid_triangles() {
+        assert_eq!(
+            WktError::WrongNumberOfCoordinates {
+                geometry: "Triangle",
+                expected: 4,
+                found: 5,
+            },
+            <Wkt<f64>>::from_str("TRIANGLE ((0 0, 0 9, 9 9, 9 0, 0 0))").unwrap_err()
+        );
+        <Wkt<f64>>::from_str("TRIANGLE ((0 0, 0 9, 9 0, 0 0), (1 1, 1 2, 2 1, 1 1))")
+            .err()
+            .unwrap();
+    }
+
+    #[test]
+    fn write_empty_triangle() {
+        let triangle: Triangle<f64> = Triangle(vec![], Dimension::XY);
+
+        assert_eq!("TRIANGLE EMPTY", format!("{}", triangle));
+    }
+
+    #[test]
+    fn write_triangle() {
+        let triangle = Triangle(
+            vec![LineString(
+                vec![
+                    Coord {
+                        x: 0.,
+                        y: 0.,
+                        z: None,
+                        m: None,
+                    },
+                    Coord {
+                        x: 0.,
+                        y: 9.,
+                        z: None,
+                        m: None,
+                    },
+                    Coord {
+                        x: 9.,
+                        y: 0.,
+                        z: None,
+                        m: None,
+                    },
+                    Coord {
+                        x: 0.,
+                        y: 0.,
+                        z: None,
+                        m: None,
+                    },
+                ],
+                Dimension::XY,
+            )],
+            Dimension::XY,
+        );
+
+        assert_eq!("TRIANGLE((0 0,0 9,9 0,0 0))", format!("{}", triangle));
+    }
+}
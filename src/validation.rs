@@ -0,0 +1,437 @@
+// Copyright 2014-2021 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::str::FromStr;
+use types::{Coord, LineString, Polygon};
+use {Geometry, Wkt, WktError, WktFloat, WktNum};
+
+impl<T> Geometry<T>
+where
+    T: WktFloat,
+{
+    /// Checks the geometry against the OGC simple feature validity rules which can be
+    /// verified per geometry: minimum coordinate counts, closed and simple polygon rings,
+    /// and polygon holes lying inside their exterior ring. Empty geometries are valid.
+    ///
+    /// Rules spanning several members, such as overlapping polygons of a multipolygon,
+    /// are not checked.
+    pub fn is_valid(&self) -> bool {
+        match self {
+            Geometry::Point(_) => true,
+            Geometry::Box(bbox) => bbox.min.x <= bbox.max.x && bbox.min.y <= bbox.max.y,
+            Geometry::LineString(linestring) => is_valid_linestring(linestring),
+            Geometry::CircularString(circularstring) => {
+                let len = circularstring.0.len();
+                len == 0 || (len >= 3 && len % 2 == 1)
+            }
+            Geometry::Polygon(polygon) => is_valid_polygon(polygon),
+            Geometry::Triangle(triangle) => {
+                triangle.0.is_empty()
+                    || (triangle.0.len() == 1
+                        && triangle.0[0].0.len() == 4
+                        && is_valid_polygon(&Polygon(triangle.0.clone(), triangle.1)))
+            }
+            Geometry::MultiPoint(_) => true,
+            Geometry::MultiLineString(multilinestring) => {
+                multilinestring.0.iter().all(is_valid_linestring)
+            }
+            Geometry::MultiPolygon(multipolygon) => multipolygon.0.iter().all(is_valid_polygon),
+            Geometry::PolyhedralSurface(surface) => surface.0.iter().all(is_valid_polygon),
+            Geometry::Tin(tin) => tin
+                .0
+                .iter()
+                .all(|triangle| triangle.clone().as_item().is_valid()),
+            Geometry::GeometryCollection(collection) => collection.0.iter().all(Geometry::is_valid),
+        }
+    }
+
+    /// Checks that every coordinate lies within the envelope from `min` to `max`, failing
+    /// with [`WktError::CoordinateOutOfBounds`] on the first which does not. The `z` is
+    /// only checked when both corners and the coordinate have one.
+    pub fn validate_bounds(&self, min: &Coord<T>, max: &Coord<T>) -> Result<(), WktError> {
+        let within = |value: T, min: T, max: T| min <= value && value <= max;
+        match self.coords().position(|c| {
+            let z_within = match (c.z, min.z, max.z) {
+                (Some(z), Some(min_z), Some(max_z)) => within(z, min_z, max_z),
+                _ => true,
+            };
+            !(within(c.x, min.x, max.x) && within(c.y, min.y, max.y) && z_within)
+        }) {
+            Some(index) => Err(WktError::CoordinateOutOfBounds { index }),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<T> Geometry<T>
+where
+    T: WktNum,
+{
+    /// Checks the minimum coordinate counts of the specification, failing on the first
+    /// part with too few: two for a linestring, four for a polygon ring, three for a
+    /// circular string and exactly four for a triangle. Empty geometries pass.
+    ///
+    /// Parsing only enforces this when [`WktParser::coordinate_counts`] is enabled.
+    ///
+    /// [`WktParser::coordinate_counts`]: crate::WktParser::coordinate_counts
+    pub fn validate_coordinate_counts(&self) -> Result<(), WktError> {
+        match self {
+            Geometry::Point(_) | Geometry::MultiPoint(_) | Geometry::Box(_) => Ok(()),
+            Geometry::LineString(linestring) => check_linestring(linestring),
+            Geometry::CircularString(circularstring) => {
+                check_min("CircularString", 3, circularstring.0.len())
+            }
+            Geometry::Polygon(polygon) => check_polygon(polygon),
+            Geometry::Triangle(triangle) => check_triangle(&triangle.0),
+            Geometry::MultiLineString(multilinestring) => {
+                multilinestring.0.iter().try_for_each(check_linestring)
+            }
+            Geometry::MultiPolygon(multipolygon) => {
+                multipolygon.0.iter().try_for_each(check_polygon)
+            }
+            Geometry::PolyhedralSurface(surface) => surface.0.iter().try_for_each(check_polygon),
+            Geometry::Tin(tin) => tin
+                .0
+                .iter()
+                .try_for_each(|triangle| check_triangle(&triangle.0)),
+            Geometry::GeometryCollection(collection) => collection
+                .0
+                .iter()
+                .try_for_each(Geometry::validate_coordinate_counts),
+        }
+    }
+}
+
+fn check_min(geometry: &'static str, min: usize, found: usize) -> Result<(), WktError> {
+    if found == 0 || found >= min {
+        Ok(())
+    } else {
+        Err(WktError::TooFewCoordinates {
+            geometry,
+            min,
+            found,
+        })
+    }
+}
+
+fn check_linestring<T: WktNum>(linestring: &LineString<T>) -> Result<(), WktError> {
+    check_min("LineString", 2, linestring.0.len())
+}
+
+fn check_polygon<T: WktNum>(polygon: &Polygon<T>) -> Result<(), WktError> {
+    polygon
+        .0
+        .iter()
+        .try_for_each(|ring| check_min("LinearRing", 4, ring.0.len()))
+}
+
+fn check_triangle<T: WktNum>(rings: &[LineString<T>]) -> Result<(), WktError> {
+    match rings.first() {
+        Some(ring) if ring.0.len() != 4 => Err(WktError::WrongNumberOfCoordinates {
+            geometry: "Triangle",
+            expected: 4,
+            found: ring.0.len(),
+        }),
+        _ => Ok(()),
+    }
+}
+
+fn is_valid_linestring<T: WktFloat>(linestring: &LineString<T>) -> bool {
+    linestring.0.len() != 1
+}
+
+fn is_valid_polygon<T: WktFloat>(polygon: &Polygon<T>) -> bool {
+    if polygon.validate().is_err() || !polygon.0.iter().all(is_simple_ring) {
+        return false;
+    }
+    match polygon.0.split_first() {
+        Some((exterior, interiors)) => interiors.iter().all(|interior| {
+            interior.0.iter().all(|c| !is_outside(c, exterior)) && !rings_cross(exterior, interior)
+        }),
+        None => true,
+    }
+}
+
+/// A closed ring is simple when only consecutive segments touch, at their shared vertex.
+fn is_simple_ring<T: WktFloat>(ring: &LineString<T>) -> bool {
+    let coords = &ring.0;
+    let segments = coords.len() - 1;
+    for i in 0..segments {
+        for j in i + 1..segments {
+            let adjacent = j == i + 1 || (i == 0 && j == segments - 1);
+            let (a, b, c, d) = (&coords[i], &coords[i + 1], &coords[j], &coords[j + 1]);
+            if adjacent {
+                // Adjacent segments only share a vertex, unless they fold back on each other.
+                if orientation(a, b, d) == 0 && orientation(c, d, a) == 0 && overlaps(a, b, c, d) {
+                    return false;
+                }
+            } else if segments_intersect(a, b, c, d) {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+/// Whether any segment of `a` properly crosses a segment of `b`.
+fn rings_cross<T: WktFloat>(a: &LineString<T>, b: &LineString<T>) -> bool {
+    a.0.windows(2).any(|s| {
+        b.0.windows(2).any(|t| {
+            let (o1, o2) = (
+                orientation(&s[0], &s[1], &t[0]),
+                orientation(&s[0], &s[1], &t[1]),
+            );
+            let (o3, o4) = (
+                orientation(&t[0], &t[1], &s[0]),
+                orientation(&t[0], &t[1], &s[1]),
+            );
+            o1 * o2 < 0 && o3 * o4 < 0
+        })
+    })
+}
+
+/// Even-odd test of `coord` against a closed `ring`; coordinates on the boundary are not
+/// outside.
+fn is_outside<T: WktFloat>(coord: &Coord<T>, ring: &LineString<T>) -> bool {
+    let mut inside = false;
+    for segment in ring.0.windows(2) {
+        let (a, b) = (&segment[0], &segment[1]);
+        if orientation(a, b, coord) == 0 && within_box(a, b, coord) {
+            return false;
+        }
+        if (a.y > coord.y) != (b.y > coord.y) {
+            let x = a.x + (coord.y - a.y) * (b.x - a.x) / (b.y - a.y);
+            if coord.x < x {
+                inside = !inside;
+            }
+        }
+    }
+    !inside
+}
+
+fn segments_intersect<T: WktFloat>(a: &Coord<T>, b: &Coord<T>, c: &Coord<T>, d: &Coord<T>) -> bool {
+    let (o1, o2) = (orientation(a, b, c), orientation(a, b, d));
+    let (o3, o4) = (orientation(c, d, a), orientation(c, d, b));
+    if o1 * o2 < 0 && o3 * o4 < 0 {
+        return true;
+    }
+    (o1 == 0 && within_box(a, b, c))
+        || (o2 == 0 && within_box(a, b, d))
+        || (o3 == 0 && within_box(c, d, a))
+        || (o4 == 0 && within_box(c, d, b))
+}
+
+/// Whether collinear segments `ab` and `cd` share more than a single point.
+fn overlaps<T: WktFloat>(a: &Coord<T>, b: &Coord<T>, c: &Coord<T>, d: &Coord<T>) -> bool {
+    let (lo, hi, p, q) = if a.x != b.x {
+        (a.x.min(b.x), a.x.max(b.x), c.x.min(d.x), c.x.max(d.x))
+    } else {
+        (a.y.min(b.y), a.y.max(b.y), c.y.min(d.y), c.y.max(d.y))
+    };
+    lo.max(p) < hi.min(q)
+}
+
+/// The sign of the turn `a` → `b` → `c`: positive counter-clockwise, negative clockwise.
+fn orientation<T: WktFloat>(a: &Coord<T>, b: &Coord<T>, c: &Coord<T>) -> i8 {
+    let cross = (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x);
+    if cross > T::zero() {
+        1
+    } else if cross < T::zero() {
+        -1
+    } else {
+        0
+    }
+}
+
+/// Whether `c` lies within the bounding box of the segment `ab`.
+fn within_box<T: WktFloat>(a: &Coord<T>, b: &Coord<T>, c: &Coord<T>) -> bool {
+    c.x >= a.x.min(b.x) && c.x <= a.x.max(b.x) && c.y >= a.y.min(b.y) && c.y <= a.y.max(b.y)
+}
+
+impl<T> Wkt<T>
+where
+    T: WktFloat + FromStr + Default,
+{
+    /// Parses `wkt_str`, then checks its coordinates against the envelope from `min` to
+    /// `max` with [`Geometry::validate_bounds`], for example to catch garbage in longitude
+    /// and latitude data.
+    pub fn from_str_with_bounds(
+        wkt_str: &str,
+        min: Coord<T>,
+        max: Coord<T>,
+    ) -> Result<Self, WktError> {
+        let wkt = Self::from_str(wkt_str)?;
+        wkt.items
+            .iter()
+            .try_for_each(|item| item.validate_bounds(&min, &max))?;
+        Ok(wkt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use types::{CircularString, Coord, Dimension, LineString, Tin, Triangle};
+    use {Geometry, Wkt, WktError};
+
+    #[test]
+    fn bounds() {
+        let corner = |x, y| Coord {
+            x,
+            y,
+            z: None,
+            m: None,
+        };
+        let parse = |s| Wkt::<f64>::from_str_with_bounds(s, corner(-180., -90.), corner(180., 90.));
+        parse("POINT (-74 40.7)").unwrap();
+        parse("LINESTRING (-180 -90, 180 90)").unwrap();
+        assert_eq!(
+            WktError::CoordinateOutOfBounds { index: 0 },
+            parse("POINT (500 1000)").unwrap_err()
+        );
+        assert_eq!(
+            WktError::CoordinateOutOfBounds { index: 2 },
+            parse("GEOMETRYCOLLECTION (POINT (1 2), LINESTRING (3 4, 5 -91))").unwrap_err()
+        );
+    }
+
+    fn is_valid(s: &str) -> bool {
+        <Wkt<f64>>::from_str(s).unwrap().items[0].is_valid()
+    }
+
+    #[test]
+    fn valid_polygon() {
+        assert!(is_valid(
+            "POLYGON ((0 0, 10 0, 10 10, 0 10, 0 0), (2 2, 2 4, 4 4, 4 2, 2 2))"
+        ));
+        assert!(is_valid("POLYGON EMPTY"));
+    }
+
+    #[test]
+    fn polygon_with_hole_outside() {
+        assert!(!is_valid(
+            "POLYGON ((0 0, 10 0, 10 10, 0 10, 0 0), (20 20, 20 24, 24 24, 24 20, 20 20))"
+        ));
+        assert!(!is_valid(
+            "POLYGON ((0 0, 10 0, 10 10, 0 10, 0 0), (5 5, 5 15, 7 15, 7 5, 5 5))"
+        ));
+    }
+
+    #[test]
+    fn invalid_rings() {
+        assert!(!is_valid("POLYGON ((0 0, 10 0, 10 10, 0 10))"));
+        assert!(!is_valid("POLYGON ((0 0, 10 10, 10 0, 0 10, 0 0))"));
+        assert!(!is_valid("POLYGON ((0 0, 10 0, 5 0, 0 0))"));
+    }
+
+    #[test]
+    fn validity_of_other_types() {
+        assert!(!is_valid("LINESTRING (0 0)"));
+        assert!(is_valid("LINESTRING (0 0, 1 1)"));
+        assert!(is_valid("TRIANGLE ((0 0, 0 9, 9 0, 0 0))"));
+        assert!(!is_valid(
+            "GEOMETRYCOLLECTION (POINT (1 2), MULTILINESTRING ((0 0, 1 1), (2 2)))"
+        ));
+    }
+
+    fn count_error(s: &str) -> WktError {
+        <Wkt<f64>>::from_str(s).unwrap().items[0]
+            .validate_coordinate_counts()
+            .err()
+            .unwrap()
+    }
+
+    fn too_few(geometry: &'static str, min: usize, found: usize) -> WktError {
+        WktError::TooFewCoordinates {
+            geometry,
+            min,
+            found,
+        }
+    }
+
+    fn coords(n: usize) -> Vec<Coord<f64>> {
+        (0..n)
+            .map(|i| Coord {
+                x: i as f64,
+                y: 0.,
+                z: None,
+                m: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn under_populated_geometries() {
+        assert_eq!(
+            too_few("LineString", 2, 1),
+            count_error("LINESTRING (10 20)")
+        );
+        assert_eq!(
+            too_few("LineString", 2, 1),
+            count_error("MULTILINESTRING ((0 0, 1 1), (10 20))")
+        );
+        assert_eq!(
+            too_few("LinearRing", 4, 3),
+            count_error("POLYGON ((0 0, 1 1, 0 0))")
+        );
+        assert_eq!(
+            too_few("LinearRing", 4, 2),
+            count_error("MULTIPOLYGON (((0 0, 1 0, 1 1, 0 0)), ((0 0, 0 0)))")
+        );
+        assert_eq!(
+            too_few("LinearRing", 4, 3),
+            count_error("POLYHEDRALSURFACE (((0 0, 1 1, 0 0)))")
+        );
+        assert_eq!(
+            too_few("LineString", 2, 1),
+            count_error("GEOMETRYCOLLECTION (POINT (1 2), LINESTRING (10 20))")
+        );
+
+        let circularstring = CircularString(coords(2), Dimension::XY).as_item();
+        assert_eq!(
+            Err(too_few("CircularString", 3, 2)),
+            circularstring.validate_coordinate_counts()
+        );
+
+        let triangle = Triangle(vec![LineString(coords(3), Dimension::XY)], Dimension::XY);
+        let wrong_triangle = Err(WktError::WrongNumberOfCoordinates {
+            geometry: "Triangle",
+            expected: 4,
+            found: 3,
+        });
+        assert_eq!(
+            wrong_triangle,
+            triangle.clone().as_item().validate_coordinate_counts()
+        );
+        assert_eq!(
+            wrong_triangle,
+            Geometry::Tin(Tin(vec![triangle], Dimension::XY)).validate_coordinate_counts()
+        );
+    }
+
+    #[test]
+    fn populated_geometries() {
+        for s in &[
+            "POINT (1 2)",
+            "LINESTRING (0 0, 1 1)",
+            "LINESTRING EMPTY",
+            "POLYGON ((0 0, 1 0, 1 1, 0 0))",
+            "TRIANGLE ((0 0, 1 0, 1 1, 0 0))",
+            "CIRCULARSTRING (0 0, 1 1, 2 0)",
+            "GEOMETRYCOLLECTION (MULTIPOINT (1 2), LINESTRING (0 0, 1 1))",
+        ] {
+            let wkt: Wkt<f64> = Wkt::from_str(s).unwrap();
+            assert_eq!(Ok(()), wkt.items[0].validate_coordinate_counts());
+        }
+    }
+}
@@ -0,0 +1,187 @@
+// Copyright 2014-2021 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use types::{
+    Box2D, CircularString, GeometryCollection, LineString, MultiLineString, MultiPoint,
+    MultiPolygon, Point, Polygon, PolyhedralSurface, Tin, Triangle,
+};
+use {Geometry, WktFloat};
+
+/// Callbacks for traversing a [`Geometry`] with [`Geometry::accept`], one per geometry type.
+///
+/// Every method does nothing by default, except those for multi geometries, polyhedral
+/// surfaces and TINs, which visit each of their parts, and the one for geometry collections,
+/// which accepts each member. A visitor therefore only overrides the methods it needs, and an
+/// override of `visit_polygon` also sees the polygons of multipolygons and nested collections.
+///
+/// ```
+/// use std::str::FromStr;
+/// use wkt::types::Polygon;
+/// use wkt::{Visitor, Wkt};
+///
+/// struct RingCounts(Vec<usize>);
+///
+/// impl Visitor<f64> for RingCounts {
+///     fn visit_polygon(&mut self, polygon: &Polygon<f64>) {
+///         self.0.push(polygon.0.len());
+///     }
+/// }
+///
+/// let wkt: Wkt<f64> = Wkt::from_str(
+///     "MULTIPOLYGON (((0 0, 4 0, 4 4, 0 0), (1 1, 2 1, 2 2, 1 1)), ((5 5, 6 5, 6 6, 5 5)))",
+/// )
+/// .unwrap();
+/// let mut counts = RingCounts(Vec::new());
+/// wkt.items[0].accept(&mut counts);
+/// assert_eq!(vec![2, 1], counts.0);
+/// ```
+pub trait Visitor<T: WktFloat> {
+    fn visit_point(&mut self, _point: &Point<T>) {}
+
+    fn visit_linestring(&mut self, _linestring: &LineString<T>) {}
+
+    fn visit_polygon(&mut self, _polygon: &Polygon<T>) {}
+
+    fn visit_multipoint(&mut self, multipoint: &MultiPoint<T>) {
+        multipoint
+            .0
+            .iter()
+            .for_each(|point| self.visit_point(point));
+    }
+
+    fn visit_multilinestring(&mut self, multilinestring: &MultiLineString<T>) {
+        multilinestring
+            .0
+            .iter()
+            .for_each(|linestring| self.visit_linestring(linestring));
+    }
+
+    fn visit_multipolygon(&mut self, multipolygon: &MultiPolygon<T>) {
+        multipolygon
+            .0
+            .iter()
+            .for_each(|polygon| self.visit_polygon(polygon));
+    }
+
+    fn visit_geometry_collection(&mut self, collection: &GeometryCollection<T>) {
+        collection
+            .0
+            .iter()
+            .for_each(|geometry| geometry.accept(self));
+    }
+
+    fn visit_triangle(&mut self, _triangle: &Triangle<T>) {}
+
+    fn visit_polyhedral_surface(&mut self, surface: &PolyhedralSurface<T>) {
+        surface
+            .0
+            .iter()
+            .for_each(|polygon| self.visit_polygon(polygon));
+    }
+
+    fn visit_tin(&mut self, tin: &Tin<T>) {
+        tin.0
+            .iter()
+            .for_each(|triangle| self.visit_triangle(triangle));
+    }
+
+    fn visit_circular_string(&mut self, _circular_string: &CircularString<T>) {}
+
+    fn visit_box(&mut self, _bbox: &Box2D<T>) {}
+}
+
+impl<T> Geometry<T>
+where
+    T: WktFloat,
+{
+    /// Calls the method of `visitor` matching the type of the geometry.
+    pub fn accept<V: Visitor<T> + ?Sized>(&self, visitor: &mut V) {
+        match self {
+            Geometry::Point(g) => visitor.visit_point(g),
+            Geometry::LineString(g) => visitor.visit_linestring(g),
+            Geometry::Polygon(g) => visitor.visit_polygon(g),
+            Geometry::MultiPoint(g) => visitor.visit_multipoint(g),
+            Geometry::MultiLineString(g) => visitor.visit_multilinestring(g),
+            Geometry::MultiPolygon(g) => visitor.visit_multipolygon(g),
+            Geometry::GeometryCollection(g) => visitor.visit_geometry_collection(g),
+            Geometry::Triangle(g) => visitor.visit_triangle(g),
+            Geometry::PolyhedralSurface(g) => visitor.visit_polyhedral_surface(g),
+            Geometry::Tin(g) => visitor.visit_tin(g),
+            Geometry::CircularString(g) => visitor.visit_circular_string(g),
+            Geometry::Box(g) => visitor.visit_box(g),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Wkt;
+
+    #[derive(Default)]
+    struct CoordCount(usize);
+
+    impl Visitor<f64> for CoordCount {
+        fn visit_point(&mut self, point: &Point<f64>) {
+            self.0 += point.0.iter().count();
+        }
+
+        fn visit_linestring(&mut self, linestring: &LineString<f64>) {
+            self.0 += linestring.0.len();
+        }
+
+        fn visit_polygon(&mut self, polygon: &Polygon<f64>) {
+            polygon
+                .0
+                .iter()
+                .for_each(|ring| self.visit_linestring(ring));
+        }
+
+        fn visit_triangle(&mut self, triangle: &Triangle<f64>) {
+            triangle
+                .0
+                .iter()
+                .for_each(|ring| self.visit_linestring(ring));
+        }
+
+        fn visit_circular_string(&mut self, circular_string: &CircularString<f64>) {
+            self.0 += circular_string.0.len();
+        }
+
+        fn visit_box(&mut self, _bbox: &Box2D<f64>) {
+            self.0 += 2;
+        }
+    }
+
+    #[test]
+    fn count_coords() {
+        let geometry = Wkt::<f64>::from_str(
+            "GEOMETRYCOLLECTION (POINT (1 2), POINT EMPTY, LINESTRING (1 2, 3 4), \
+             POLYGON ((0 0, 1 0, 1 1, 0 0)), MULTIPOINT ((1 2), (3 4)), \
+             MULTILINESTRING ((1 2, 3 4), (5 6, 7 8, 9 10)), \
+             MULTIPOLYGON (((0 0, 4 0, 4 4, 0 0), (1 1, 2 1, 2 2, 1 1))), \
+             TRIANGLE ((0 0, 1 0, 0 1, 0 0)), \
+             POLYHEDRALSURFACE (((0 0, 1 0, 1 1, 0 0))), \
+             TIN (((0 0, 1 0, 0 1, 0 0)), ((1 0, 1 1, 0 1, 1 0))), \
+             CIRCULARSTRING (0 0, 1 1, 2 0), \
+             GEOMETRYCOLLECTION (POINT (5 6)))",
+        )
+        .unwrap()
+        .items
+        .remove(0);
+        let mut count = CoordCount::default();
+        geometry.accept(&mut count);
+        assert_eq!(geometry.coord_count(), count.0);
+        assert_eq!(42, count.0);
+    }
+}
@@ -0,0 +1,610 @@
+// Copyright 2014-2021 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Well-known binary (WKB) output, following the ISO type codes for `Z`, `M` and `ZM`
+//! geometries, and the PostGIS extended WKB (EWKB) dialect carrying an SRID. An empty
+//! point is written with `NaN` ordinates, and a `BOX` as its rectangle polygon.
+
+use parser::DEFAULT_MAX_DEPTH;
+use std::io::{self, Write};
+use types::{
+    CircularString, Coord, Dimension, GeometryCollection, LineString, MultiLineString, MultiPoint,
+    MultiPolygon, Point, Polygon, PolyhedralSurface, Tin, Triangle,
+};
+use {Geometry, WktError, WktFloat};
+
+/// The EWKB type code flags for `Z` and `M` ordinates and for a following SRID
+const EWKB_Z: u32 = 0x8000_0000;
+const EWKB_M: u32 = 0x4000_0000;
+const EWKB_SRID: u32 = 0x2000_0000;
+
+/// The byte order of WKB output.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Endianness {
+    BigEndian,
+    LittleEndian,
+}
+
+impl<T> Geometry<T>
+where
+    T: WktFloat,
+{
+    /// Streams the geometry as WKB to `w`. A geometry whose coordinates differ in dimension
+    /// fails with an [`io::ErrorKind::InvalidInput`] error.
+    pub fn write_wkb<W: Write>(&self, w: &mut W, endianness: Endianness) -> io::Result<()> {
+        let dim = self
+            .dimension()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        WkbWriter {
+            w,
+            endianness,
+            dim,
+            ewkb: false,
+            srid: None,
+        }
+        .geometry(self)
+    }
+
+    /// The geometry as WKB, see [`Geometry::write_wkb`].
+    pub fn to_wkb(&self, endianness: Endianness) -> Result<Vec<u8>, WktError> {
+        let mut out = Vec::new();
+        let dim = self.dimension()?;
+        WkbWriter {
+            w: &mut out,
+            endianness,
+            dim,
+            ewkb: false,
+            srid: None,
+        }
+        .geometry(self)
+        .expect("writing to a Vec cannot fail");
+        Ok(out)
+    }
+
+    /// The geometry as PostGIS extended WKB, flagging `Z` and `M` ordinates in the type
+    /// codes and embedding `srid` in the outermost geometry when given.
+    pub fn to_ewkb(&self, endianness: Endianness, srid: Option<u32>) -> Result<Vec<u8>, WktError> {
+        let mut out = Vec::new();
+        let dim = self.dimension()?;
+        WkbWriter {
+            w: &mut out,
+            endianness,
+            dim,
+            ewkb: true,
+            srid,
+        }
+        .geometry(self)
+        .expect("writing to a Vec cannot fail");
+        Ok(out)
+    }
+}
+
+impl<T> Geometry<T>
+where
+    T: WktFloat + Default,
+{
+    /// Reads a geometry and its SRID, if any, from extended WKB as written by
+    /// [`Geometry::to_ewkb`]. Plain WKB with ISO type codes is read too. Polygons keep
+    /// their type, so a `BOX` written as WKB reads back as a `POLYGON`.
+    pub fn from_ewkb(bytes: &[u8]) -> Result<(Geometry<T>, Option<u32>), WktError> {
+        let mut reader = WkbReader {
+            bytes,
+            big_endian: false,
+            depth: 0,
+        };
+        let (geometry, srid) = reader.geometry()?;
+        if !reader.bytes.is_empty() {
+            return Err(WktError::InvalidWkb("trailing bytes"));
+        }
+        Ok((geometry, srid))
+    }
+}
+
+struct WkbWriter<'a, W: 'a> {
+    w: &'a mut W,
+    endianness: Endianness,
+    dim: Dimension,
+    ewkb: bool,
+    /// The SRID still to be written, in the header of the outermost geometry
+    srid: Option<u32>,
+}
+
+impl<'a, W: Write> WkbWriter<'a, W> {
+    fn header(&mut self, code: u32) -> io::Result<()> {
+        let code = if self.ewkb {
+            let mut code = code;
+            if self.dim.has_z() {
+                code |= EWKB_Z;
+            }
+            if self.dim.has_m() {
+                code |= EWKB_M;
+            }
+            if self.srid.is_some() {
+                code |= EWKB_SRID;
+            }
+            code
+        } else {
+            code + match self.dim {
+                Dimension::XY => 0,
+                Dimension::XYZ => 1000,
+                Dimension::XYM => 2000,
+                Dimension::XYZM => 3000,
+            }
+        };
+        let order = match self.endianness {
+            Endianness::BigEndian => 0,
+            Endianness::LittleEndian => 1,
+        };
+        self.w.write_all(&[order])?;
+        self.u32(code)?;
+        match self.srid.take() {
+            Some(srid) => self.u32(srid),
+            None => Ok(()),
+        }
+    }
+
+    fn u32(&mut self, value: u32) -> io::Result<()> {
+        match self.endianness {
+            Endianness::BigEndian => self.w.write_all(&value.to_be_bytes()),
+            Endianness::LittleEndian => self.w.write_all(&value.to_le_bytes()),
+        }
+    }
+
+    fn len(&mut self, len: usize) -> io::Result<()> {
+        if len > u32::MAX as usize {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "too many elements for WKB",
+            ));
+        }
+        self.u32(len as u32)
+    }
+
+    fn f64<T: WktFloat>(&mut self, value: Option<T>) -> io::Result<()> {
+        let value = value.and_then(|v| v.to_f64()).unwrap_or(f64::NAN);
+        match self.endianness {
+            Endianness::BigEndian => self.w.write_all(&value.to_be_bytes()),
+            Endianness::LittleEndian => self.w.write_all(&value.to_le_bytes()),
+        }
+    }
+
+    fn coord<T: WktFloat>(&mut self, coord: Option<&Coord<T>>) -> io::Result<()> {
+        self.f64(coord.map(|c| c.x))?;
+        self.f64(coord.map(|c| c.y))?;
+        if self.dim.has_z() {
+            self.f64(coord.and_then(|c| c.z))?;
+        }
+        if self.dim.has_m() {
+            self.f64(coord.and_then(|c| c.m))?;
+        }
+        Ok(())
+    }
+
+    fn coords<T: WktFloat>(&mut self, coords: &[Coord<T>]) -> io::Result<()> {
+        self.len(coords.len())?;
+        coords.iter().try_for_each(|c| self.coord(Some(c)))
+    }
+
+    fn rings<T: WktFloat>(&mut self, rings: &[LineString<T>]) -> io::Result<()> {
+        self.len(rings.len())?;
+        rings.iter().try_for_each(|r| self.coords(&r.0))
+    }
+
+    fn polygons<T: WktFloat>(&mut self, polygons: &[Polygon<T>]) -> io::Result<()> {
+        self.len(polygons.len())?;
+        for polygon in polygons {
+            self.header(3)?;
+            self.rings(&polygon.0)?;
+        }
+        Ok(())
+    }
+
+    fn geometry<T: WktFloat>(&mut self, geometry: &Geometry<T>) -> io::Result<()> {
+        match geometry {
+            Geometry::Point(point) => {
+                self.header(1)?;
+                self.coord(point.0.as_ref())
+            }
+            Geometry::LineString(linestring) => {
+                self.header(2)?;
+                self.coords(&linestring.0)
+            }
+            Geometry::Polygon(polygon) => {
+                self.header(3)?;
+                self.rings(&polygon.0)
+            }
+            Geometry::Box(bbox) => {
+                self.header(3)?;
+                self.rings(&bbox.to_polygon().0)
+            }
+            Geometry::MultiPoint(multipoint) => {
+                self.header(4)?;
+                self.len(multipoint.0.len())?;
+                for point in &multipoint.0 {
+                    self.header(1)?;
+                    self.coord(point.0.as_ref())?;
+                }
+                Ok(())
+            }
+            Geometry::MultiLineString(multilinestring) => {
+                self.header(5)?;
+                self.len(multilinestring.0.len())?;
+                for linestring in &multilinestring.0 {
+                    self.header(2)?;
+                    self.coords(&linestring.0)?;
+                }
+                Ok(())
+            }
+            Geometry::MultiPolygon(multipolygon) => {
+                self.header(6)?;
+                self.polygons(&multipolygon.0)
+            }
+            Geometry::GeometryCollection(collection) => {
+                self.header(7)?;
+                self.len(collection.0.len())?;
+                collection.0.iter().try_for_each(|g| self.geometry(g))
+            }
+            Geometry::CircularString(circularstring) => {
+                self.header(8)?;
+                self.coords(&circularstring.0)
+            }
+            Geometry::PolyhedralSurface(surface) => {
+                self.header(15)?;
+                self.polygons(&surface.0)
+            }
+            Geometry::Tin(tin) => {
+                self.header(16)?;
+                self.len(tin.0.len())?;
+                for triangle in &tin.0 {
+                    self.header(17)?;
+                    self.rings(&triangle.0)?;
+                }
+                Ok(())
+            }
+            Geometry::Triangle(triangle) => {
+                self.header(17)?;
+                self.rings(&triangle.0)
+            }
+        }
+    }
+}
+
+struct WkbReader<'a> {
+    bytes: &'a [u8],
+    /// The byte order of the geometry being read, which each header sets
+    big_endian: bool,
+    /// How many multi geometries and collections enclose the geometry being read
+    depth: usize,
+}
+
+impl<'a> WkbReader<'a> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], WktError> {
+        if self.bytes.len() < N {
+            return Err(WktError::InvalidWkb("unexpected end of input"));
+        }
+        let (head, tail) = self.bytes.split_at(N);
+        self.bytes = tail;
+        let mut buf = [0; N];
+        buf.copy_from_slice(head);
+        Ok(buf)
+    }
+
+    fn u32(&mut self) -> Result<u32, WktError> {
+        let buf = self.take()?;
+        Ok(match self.big_endian {
+            true => u32::from_be_bytes(buf),
+            false => u32::from_le_bytes(buf),
+        })
+    }
+
+    fn f64<T: WktFloat>(&mut self) -> Result<T, WktError> {
+        let buf = self.take()?;
+        let value = match self.big_endian {
+            true => f64::from_be_bytes(buf),
+            false => f64::from_le_bytes(buf),
+        };
+        T::from(value).ok_or(WktError::InvalidWkb("ordinate out of range"))
+    }
+
+    /// Reads a byte order and type code, returning the base type code, the dimension and
+    /// the SRID.
+    fn header(&mut self) -> Result<(u32, Dimension, Option<u32>), WktError> {
+        self.big_endian = match self.take::<1>()?[0] {
+            0 => true,
+            1 => false,
+            _ => return Err(WktError::InvalidWkb("unknown byte order")),
+        };
+        let code = self.u32()?;
+        let srid = match code & EWKB_SRID {
+            0 => None,
+            _ => Some(self.u32()?),
+        };
+        let iso = code & !(EWKB_Z | EWKB_M | EWKB_SRID);
+        let z = code & EWKB_Z != 0 || matches!(iso / 1000, 1 | 3);
+        let m = code & EWKB_M != 0 || matches!(iso / 1000, 2 | 3);
+        let dim = match (z, m) {
+            (false, false) => Dimension::XY,
+            (true, false) => Dimension::XYZ,
+            (false, true) => Dimension::XYM,
+            (true, true) => Dimension::XYZM,
+        };
+        Ok((iso % 1000, dim, srid))
+    }
+
+    fn len(&mut self) -> Result<usize, WktError> {
+        Ok(self.u32()? as usize)
+    }
+
+    fn coord<T: WktFloat>(&mut self, dim: Dimension) -> Result<Coord<T>, WktError> {
+        Ok(Coord {
+            x: self.f64()?,
+            y: self.f64()?,
+            z: if dim.has_z() { Some(self.f64()?) } else { None },
+            m: if dim.has_m() { Some(self.f64()?) } else { None },
+        })
+    }
+
+    fn coords<T: WktFloat>(&mut self, dim: Dimension) -> Result<Vec<Coord<T>>, WktError> {
+        let len = self.len()?;
+        // Every coordinate takes at least 16 bytes, which bounds the allocation.
+        let mut coords = Vec::with_capacity(len.min(self.bytes.len() / 16));
+        for _ in 0..len {
+            coords.push(self.coord(dim)?);
+        }
+        Ok(coords)
+    }
+
+    fn rings<T: WktFloat>(&mut self, dim: Dimension) -> Result<Vec<LineString<T>>, WktError> {
+        (0..self.len()?)
+            .map(|_| self.coords(dim).map(|coords| LineString(coords, dim)))
+            .collect()
+    }
+
+    /// Reads the members of a multi geometry, each of which `f` unwraps. Multi geometries
+    /// count towards [`DEFAULT_MAX_DEPTH`] like collections, since a malformed member may
+    /// be one too.
+    fn members<T, M, F>(&mut self, f: F) -> Result<Vec<M>, WktError>
+    where
+        T: WktFloat + Default,
+        F: Fn(Geometry<T>) -> Option<M>,
+    {
+        if self.depth >= DEFAULT_MAX_DEPTH {
+            return Err(WktError::InvalidWkb("collections nested too deeply"));
+        }
+        self.depth += 1;
+        let members = (0..self.len()?)
+            .map(|_| {
+                let (member, _) = self.geometry()?;
+                f(member).ok_or(WktError::InvalidWkb("unexpected member type"))
+            })
+            .collect();
+        self.depth -= 1;
+        members
+    }
+
+    fn geometry<T: WktFloat + Default>(&mut self) -> Result<(Geometry<T>, Option<u32>), WktError> {
+        let (code, dim, srid) = self.header()?;
+        let geometry = match code {
+            1 => {
+                let coord = self.coord::<T>(dim)?;
+                let empty = coord.x.is_nan() && coord.y.is_nan();
+                Geometry::Point(Point(if empty { None } else { Some(coord) }, dim))
+            }
+            2 => Geometry::LineString(LineString(self.coords(dim)?, dim)),
+            3 => Geometry::Polygon(Polygon(self.rings(dim)?, dim)),
+            4 => Geometry::MultiPoint(MultiPoint(
+                self.members(|member| match member {
+                    Geometry::Point(point) => Some(point),
+                    _ => None,
+                })?,
+                dim,
+            )),
+            5 => Geometry::MultiLineString(MultiLineString(
+                self.members(|member| match member {
+                    Geometry::LineString(linestring) => Some(linestring),
+                    _ => None,
+                })?,
+                dim,
+            )),
+            6 => Geometry::MultiPolygon(MultiPolygon(
+                self.members(|member| match member {
+                    Geometry::Polygon(polygon) => Some(polygon),
+                    _ => None,
+                })?,
+                dim,
+            )),
+            7 => Geometry::GeometryCollection(GeometryCollection(self.members(Some)?, dim)),
+            8 => Geometry::CircularString(CircularString(self.coords(dim)?, dim)),
+            15 => Geometry::PolyhedralSurface(PolyhedralSurface(
+                self.members(|member| match member {
+                    Geometry::Polygon(polygon) => Some(polygon),
+                    _ => None,
+                })?,
+                dim,
+            )),
+            16 => Geometry::Tin(Tin(
+                self.members(|member| match member {
+                    Geometry::Triangle(triangle) => Some(triangle),
+                    _ => None,
+                })?,
+                dim,
+            )),
+            17 => Geometry::Triangle(Triangle(self.rings(dim)?, dim)),
+            _ => return Err(WktError::InvalidWkb("unsupported geometry type")),
+        };
+        Ok((geometry, srid))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Endianness;
+    use parser::DEFAULT_MAX_DEPTH;
+    use std::io::Cursor;
+    use types::{Coord, Dimension, LineString};
+    use Geometry;
+    use Wkt;
+    use WktError;
+
+    #[test]
+    fn point_ewkb_with_srid() {
+        let wkt: Wkt<f64> = Wkt::from_str("POINT (1 2)").unwrap();
+        let bytes = wkt.items[0]
+            .to_ewkb(Endianness::LittleEndian, Some(4326))
+            .unwrap();
+        let mut expected = vec![1, 0x01, 0, 0, 0x20, 0xE6, 0x10, 0, 0];
+        for value in &[1f64, 2.] {
+            expected.extend_from_slice(&value.to_le_bytes());
+        }
+        assert_eq!(expected, bytes);
+
+        let (geometry, srid) = Geometry::<f64>::from_ewkb(&bytes).unwrap();
+        assert_eq!(Some(4326), srid);
+        assert_eq!("POINT(1 2)", geometry.to_string());
+    }
+
+    #[test]
+    fn dimension_flags_ewkb() {
+        let wkt: Wkt<f64> = Wkt::from_str("LINESTRING ZM (1 2 3 4, 5 6 7 8)").unwrap();
+        let bytes = wkt.items[0]
+            .to_ewkb(Endianness::BigEndian, Some(3857))
+            .unwrap();
+        assert_eq!([0, 0xE0, 0, 0, 0x02], bytes[..5]);
+        assert_eq!(3857u32.to_be_bytes(), bytes[5..9]);
+        assert_eq!(2u32.to_be_bytes(), bytes[9..13]);
+        assert_eq!(13 + 2 * 32, bytes.len());
+
+        let wkt: Wkt<f64> = Wkt::from_str("POINT M (1 2 3)").unwrap();
+        let bytes = wkt.items[0].to_ewkb(Endianness::BigEndian, None).unwrap();
+        assert_eq!([0, 0x40, 0, 0, 0x01], bytes[..5]);
+        assert_eq!(5 + 24, bytes.len());
+    }
+
+    #[test]
+    fn ewkb_round_trip() {
+        for input in &[
+            "POINT EMPTY",
+            "POINT Z(1 2 3)",
+            "MULTIPOINT M((1 2 3),(4 5 6))",
+            "POLYGON((0 0,1 0,1 1,0 0),(0.1 0.1,0.2 0.1,0.2 0.2,0.1 0.1))",
+            "MULTIPOLYGON(((0 0,1 0,1 1,0 0)),((5 5,6 5,6 6,5 5)))",
+            "GEOMETRYCOLLECTION Z(POINT Z(1 2 3),LINESTRING Z(1 2 3,4 5 6))",
+            "TIN(((0 0,1 0,0 1,0 0)))",
+        ] {
+            let wkt: Wkt<f64> = Wkt::from_str(input).unwrap();
+            for &endianness in &[Endianness::BigEndian, Endianness::LittleEndian] {
+                for &srid in &[None, Some(4326)] {
+                    let bytes = wkt.items[0].to_ewkb(endianness, srid).unwrap();
+                    let (geometry, read_srid) = Geometry::<f64>::from_ewkb(&bytes).unwrap();
+                    assert_eq!(srid, read_srid);
+                    assert_eq!(format!("{:?}", wkt.items[0]), format!("{:?}", geometry));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn read_iso_wkb() {
+        let wkt: Wkt<f64> = Wkt::from_str("LINESTRING Z (1 2 3, 4 5 6)").unwrap();
+        let bytes = wkt.items[0].to_wkb(Endianness::LittleEndian).unwrap();
+        let (geometry, srid) = Geometry::<f64>::from_ewkb(&bytes).unwrap();
+        assert_eq!(None, srid);
+        assert_eq!(format!("{:?}", wkt.items[0]), format!("{:?}", geometry));
+    }
+
+    #[test]
+    fn invalid_ewkb() {
+        let wkt: Wkt<f64> = Wkt::from_str("POINT (1 2)").unwrap();
+        let mut bytes = wkt.items[0]
+            .to_ewkb(Endianness::LittleEndian, Some(4326))
+            .unwrap();
+        assert_eq!(
+            WktError::InvalidWkb("unexpected end of input"),
+            Geometry::<f64>::from_ewkb(&bytes[..bytes.len() - 1]).unwrap_err()
+        );
+        bytes.push(0);
+        assert_eq!(
+            WktError::InvalidWkb("trailing bytes"),
+            Geometry::<f64>::from_ewkb(&bytes).unwrap_err()
+        );
+        assert_eq!(
+            WktError::InvalidWkb("unsupported geometry type"),
+            Geometry::<f64>::from_ewkb(&[1, 99, 0, 0, 0]).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn nesting_limit_wkb() {
+        let nested = |depth| {
+            let mut bytes = [1, 7, 0, 0, 0, 1, 0, 0, 0].repeat(depth);
+            bytes.extend_from_slice(&[1, 1, 0, 0, 0]);
+            bytes.extend_from_slice(&[0; 16]);
+            bytes
+        };
+        Geometry::<f64>::from_ewkb(&nested(DEFAULT_MAX_DEPTH)).unwrap();
+        assert_eq!(
+            WktError::InvalidWkb("collections nested too deeply"),
+            Geometry::<f64>::from_ewkb(&nested(200_000)).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn large_linestring_wkb() {
+        let coords = (0..10_000)
+            .map(|i| Coord {
+                x: i as f64,
+                y: -i as f64,
+                z: None,
+                m: None,
+            })
+            .collect();
+        let geometry = Geometry::LineString(LineString(coords, Dimension::XY));
+        let mut cursor = Cursor::new(Vec::new());
+        geometry
+            .write_wkb(&mut cursor, Endianness::LittleEndian)
+            .unwrap();
+        let bytes = cursor.into_inner();
+
+        assert_eq!(1 + 4 + 4 + 10_000 * 16, bytes.len());
+        assert_eq!(1, bytes[0]);
+        assert_eq!([2, 0, 0, 0], bytes[1..5]);
+        assert_eq!(10_000u32.to_le_bytes(), bytes[5..9]);
+        assert_eq!(
+            9999f64.to_le_bytes(),
+            bytes[bytes.len() - 16..bytes.len() - 8]
+        );
+    }
+
+    #[test]
+    fn point_z_wkb() {
+        let wkt: Wkt<f64> = Wkt::from_str("POINT Z (1 2 3)").unwrap();
+        let mut expected = vec![0, 0, 0, 0x03, 0xE9];
+        for value in &[1f64, 2., 3.] {
+            expected.extend_from_slice(&value.to_be_bytes());
+        }
+        assert_eq!(
+            expected,
+            wkt.items[0].to_wkb(Endianness::BigEndian).unwrap()
+        );
+    }
+
+    #[test]
+    fn multipoint_wkb() {
+        let wkt: Wkt<f64> = Wkt::from_str("MULTIPOINT ((1 2), (3 4))").unwrap();
+        let bytes = wkt.items[0].to_wkb(Endianness::LittleEndian).unwrap();
+        assert_eq!(1 + 4 + 4 + 2 * (1 + 4 + 16), bytes.len());
+        assert_eq!([4, 0, 0, 0], bytes[1..5]);
+        assert_eq!([1, 1, 0, 0, 0], bytes[9..14]);
+    }
+}